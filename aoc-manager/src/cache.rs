@@ -0,0 +1,664 @@
+use std::{
+    fmt,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use aoc_utils::{encrypted_path, CacheKey, ENCRYPTED_EXTENSION};
+use serde::{Deserialize, Serialize};
+use toml::{Table, Value};
+
+use crate::{
+    config::Config,
+    http::HttpClient,
+    leaderboard::Leaderboard,
+    puzzle::Puzzle,
+    submit::{Guesses, Verdict},
+    validate, Challenge, RuntimeError, Year,
+};
+
+/// Minimum time between two requests for the same private leaderboard, as
+/// asked by the AoC maintainers.
+pub const LEADERBOARD_REFRESH: Duration = Duration::from_secs(15 * 60);
+
+/// Directory of the cached private leaderboards, within the cache directory.
+const LEADERBOARDS_DIR: &str = "leaderboards";
+
+/// Names that can't be given to accounts in the sessions file: the cache
+/// directories of named accounts sit next to the leaderboards directory, and
+/// the default account is displayed as `default`.
+const RESERVED_ACCOUNT_NAMES: [&str; 2] = ["default", LEADERBOARDS_DIR];
+
+/// An Advent of Code account, whose inputs (and answers) are cached separately
+/// from the other accounts'.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Account {
+    /// The account whose session is in the AOC_SESSION environment variable
+    /// or in the .aoc-token file. Its inputs are the ones the solutions load.
+    Default,
+    /// An account whose session is listed in the sessions file.
+    Named(String),
+}
+
+impl fmt::Display for Account {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Account::Default => write!(f, "default"),
+            Account::Named(name) => write!(f, "{name}"),
+        }
+    }
+}
+
+fn is_valid_account_name(name: &str) -> bool {
+    !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+        && !RESERVED_ACCOUNT_NAMES.contains(&name)
+}
+
+/// The answers of both parts of a challenge, as printed by a solution or as
+/// recorded for an account.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Answers {
+    pub part_1: Option<String>,
+    pub part_2: Option<String>,
+}
+
+impl Answers {
+    /// Extracts the answers from the output of a solution, which prints them
+    /// on lines of the form `Part 1: <answer>`.
+    pub fn from_output(output: &str) -> Self {
+        output.lines().fold(Self::default(), |mut answers, line| {
+            if let Some(answer) = line.strip_prefix("Part 1: ") {
+                answers.part_1 = Some(answer.trim().to_string());
+            } else if let Some(answer) = line.strip_prefix("Part 2: ") {
+                answers.part_2 = Some(answer.trim().to_string());
+            }
+
+            answers
+        })
+    }
+
+    pub fn from_table(table: &Table) -> Self {
+        let get = |key| table.get(key).and_then(Value::as_str).map(str::to_string);
+
+        Self {
+            part_1: get("part_1"),
+            part_2: get("part_2"),
+        }
+    }
+
+    pub fn to_table(&self) -> Table {
+        [("part_1", &self.part_1), ("part_2", &self.part_2)]
+            .into_iter()
+            .filter_map(|(key, answer)| {
+                answer
+                    .clone()
+                    .map(|answer| (key.to_string(), Value::String(answer)))
+            })
+            .collect()
+    }
+}
+
+/// The results of the last measured run of a challenge, on the input of the
+/// default account.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+#[serde(default)]
+pub struct RunRecord {
+    /// Whether the tests of the crate, usually run on the examples, passed.
+    pub tests_passed: Option<bool>,
+    /// Time taken by the solution, in milliseconds.
+    pub runtime_ms: Option<u64>,
+}
+
+pub struct InputCache<'a> {
+    config: &'a Config,
+    http: HttpClient,
+}
+
+impl<'a> InputCache<'a> {
+    pub fn new(config: &'a Config) -> Self {
+        Self::with_client(config, HttpClient::new(&config.http))
+    }
+
+    /// Creates a cache that sends its requests through the given client, such
+    /// as one with a fake transport.
+    pub fn with_client(config: &'a Config, http: HttpClient) -> Self {
+        Self { config, http }
+    }
+
+    pub fn cache_dir(&self, account: &Account) -> PathBuf {
+        match account {
+            Account::Default => self.config.paths.cache.clone(),
+            Account::Named(name) => self.config.paths.cache.join(name),
+        }
+    }
+
+    /// Reads the named sessions from the sessions file, which has the form:
+    ///
+    /// ```toml
+    /// [sessions]
+    /// alice = "session=..."
+    /// ```
+    ///
+    /// A missing file is the same as an empty one. Account names are used as
+    /// directory names in the cache, so they can only contain ASCII letters,
+    /// digits, `-` and `_`, and can't be a reserved name.
+    pub fn get_named_sessions(&self) -> Result<Table, RuntimeError> {
+        let table = match std::fs::read_to_string(&self.config.paths.sessions) {
+            Ok(s) => s.parse::<Table>()?,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Table::new()),
+            Err(e) => return Err(e.into()),
+        };
+
+        match table.get("sessions") {
+            None => Ok(Table::new()),
+            Some(Value::Table(sessions)) if sessions.values().all(Value::is_str) => {
+                if let Some(name) = sessions.keys().find(|name| !is_valid_account_name(name)) {
+                    return Err(RuntimeError::InvalidAccountName(name.clone()));
+                }
+
+                Ok(sessions.clone())
+            }
+            Some(_) => Err(RuntimeError::InvalidSessionsFile),
+        }
+    }
+
+    pub fn get_session(&self, account: &Account) -> Result<String, RuntimeError> {
+        let session = match account {
+            Account::Default => {
+                if let Ok(x) = std::env::var("AOC_SESSION") {
+                    x
+                } else if let Ok(x) = std::fs::read_to_string(&self.config.paths.session) {
+                    x
+                } else {
+                    return Err(RuntimeError::MissingSessionKey);
+                }
+            }
+            Account::Named(name) => self
+                .get_named_sessions()?
+                .get(name)
+                .and_then(Value::as_str)
+                .map(str::to_string)
+                .ok_or_else(|| RuntimeError::UnknownAccount(name.clone()))?,
+        };
+
+        Ok(session.trim().to_string())
+    }
+
+    /// Returns the default account followed by every account of the sessions
+    /// file.
+    pub fn get_accounts(&self) -> Result<Vec<Account>, RuntimeError> {
+        Ok(std::iter::once(Account::Default)
+            .chain(
+                self.get_named_sessions()?
+                    .keys()
+                    .cloned()
+                    .map(Account::Named),
+            )
+            .collect())
+    }
+
+    pub fn input_path(&self, account: &Account, challenge: &Challenge) -> PathBuf {
+        self.cache_dir(account).join(challenge.input_name())
+    }
+
+    pub fn answers_path(&self, account: &Account, challenge: &Challenge) -> PathBuf {
+        self.cache_dir(account).join(challenge.answers_name())
+    }
+
+    /// Whether the input of an account is cached, either in plain text or
+    /// encrypted.
+    pub fn has_input(&self, account: &Account, challenge: &Challenge) -> bool {
+        let path = self.input_path(account, challenge);
+
+        path.is_file() || encrypted_path(&path).is_file()
+    }
+
+    /// Returns the path of the cached input of an account, for the tools that
+    /// read it from a file. If only its encrypted copy is cached, it is
+    /// decrypted first. Returns `None` if the input isn't cached.
+    pub fn cached_input(
+        &self,
+        account: &Account,
+        challenge: &Challenge,
+    ) -> Result<Option<PathBuf>, RuntimeError> {
+        let path = self.input_path(account, challenge);
+
+        if !path.is_file() {
+            match self.read_cached(&path)? {
+                Some(input) => std::fs::write(&path, input)?,
+                None => return Ok(None),
+            }
+        }
+
+        Ok(Some(path))
+    }
+
+    pub fn get_present(&self, account: &Account) -> Result<Vec<Challenge>, RuntimeError> {
+        std::fs::create_dir_all(self.cache_dir(account))?;
+
+        std::fs::read_dir(self.cache_dir(account))?
+            .map(|entry| {
+                let entry = entry?;
+                let meta = entry.metadata()?;
+
+                Ok(meta.is_file().then(|| entry.path()).and_then(|path| {
+                    let name = path.file_name()?.to_string_lossy().into_owned();
+                    let name = name
+                        .strip_suffix(&format!(".{ENCRYPTED_EXTENSION}"))
+                        .unwrap_or(&name);
+
+                    Challenge::from_input_name(name).ok()
+                }))
+            })
+            .filter_map(|r| match r {
+                Err(e) => Some(Err(e)),
+                Ok(None) => None,
+                Ok(Some(v)) => Some(Ok(v)),
+            })
+            .collect::<Result<Vec<_>, RuntimeError>>()
+            .map(|mut challenges| {
+                // An input can be cached both in plain text and encrypted
+                challenges.sort();
+                challenges.dedup();
+                challenges
+            })
+    }
+
+    /// Returns the key of the encrypted cache, from the `AOC_CACHE_KEY`
+    /// environment variable or from the key file.
+    pub fn cache_key(&self) -> Result<CacheKey, RuntimeError> {
+        CacheKey::load(&self.config.paths.cache_key)?
+            .ok_or_else(|| RuntimeError::MissingCacheKey(self.config.paths.cache_key.clone()))
+    }
+
+    fn decrypt_file(&self, path: &Path) -> Result<String, RuntimeError> {
+        let plaintext = self
+            .cache_key()?
+            .decrypt(&std::fs::read(path)?)
+            .map_err(|source| RuntimeError::Decryption {
+                path: path.to_path_buf(),
+                source,
+            })?;
+
+        String::from_utf8(plaintext)
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e).into())
+    }
+
+    /// Reads a cached file, or decrypts its encrypted copy if only that one is
+    /// cached. Returns `None` if neither is.
+    pub fn read_cached(&self, path: &Path) -> Result<Option<String>, RuntimeError> {
+        match std::fs::read_to_string(path) {
+            Ok(s) => return Ok(Some(s)),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+            Err(e) => return Err(e.into()),
+        }
+
+        let encrypted = encrypted_path(path);
+        if encrypted.is_file() {
+            self.decrypt_file(&encrypted).map(Some)
+        } else {
+            Ok(None)
+        }
+    }
+
+    /// Writes a cached file, along with its encrypted copy if `cache.encrypt`
+    /// is set.
+    fn write_cached(&self, path: &Path, contents: &str) -> Result<(), RuntimeError> {
+        if self.config.cache.encrypt {
+            std::fs::write(
+                encrypted_path(path),
+                self.cache_key()?.encrypt(contents.as_bytes()),
+            )?;
+        }
+
+        std::fs::write(path, contents)?;
+
+        Ok(())
+    }
+
+    /// Returns the files of the cache of an account that are private to it,
+    /// and that are encrypted by `cache encrypt`: its inputs and its answers.
+    /// Encrypted copies are returned under the name of their plain text file.
+    fn private_files(&self, account: &Account) -> Result<Vec<PathBuf>, RuntimeError> {
+        let dir = self.cache_dir(account);
+        if !dir.is_dir() {
+            return Ok(Vec::new());
+        }
+
+        let mut files = std::fs::read_dir(&dir)?
+            .map(|entry| Ok(entry?.file_name().to_string_lossy().into_owned()))
+            .collect::<Result<Vec<_>, std::io::Error>>()?
+            .into_iter()
+            .map(|name| {
+                name.strip_suffix(&format!(".{ENCRYPTED_EXTENSION}"))
+                    .map_or(name.clone(), str::to_string)
+            })
+            .filter(|name| {
+                Challenge::from_input_name(name).is_ok()
+                    || name.ends_with(".answers.toml")
+                    || name.ends_with(".guesses.toml")
+            })
+            .map(|name| dir.join(name))
+            .collect::<Vec<_>>();
+
+        files.sort();
+        files.dedup();
+
+        Ok(files)
+    }
+
+    /// Writes the encrypted copy of every cached input and answers file of
+    /// every account, so that they can be committed. Returns the number of
+    /// encrypted copies that were created or updated.
+    pub fn encrypt_all(&self) -> Result<usize, RuntimeError> {
+        let key = self.cache_key()?;
+        let mut written = 0;
+
+        for account in self.get_accounts()? {
+            for path in self.private_files(&account)? {
+                let Ok(contents) = std::fs::read(&path) else {
+                    continue;
+                };
+                let encrypted = encrypted_path(&path);
+                let data = key.encrypt(&contents);
+
+                // The encryption is deterministic, so unchanged files are kept
+                if std::fs::read(&encrypted).ok() != Some(data.clone()) {
+                    std::fs::write(&encrypted, data)?;
+                    written += 1;
+                }
+            }
+        }
+
+        Ok(written)
+    }
+
+    /// Restores the plain text files of the cache from their encrypted copies,
+    /// for every account. Existing files are only replaced with `overwrite`.
+    /// Returns the number of files written.
+    pub fn decrypt_all(&self, overwrite: bool) -> Result<usize, RuntimeError> {
+        let mut written = 0;
+
+        for account in self.get_accounts()? {
+            for path in self.private_files(&account)? {
+                let encrypted = encrypted_path(&path);
+
+                if encrypted.is_file() && (overwrite || !path.exists()) {
+                    std::fs::write(&path, self.decrypt_file(&encrypted)?)?;
+                    written += 1;
+                }
+            }
+        }
+
+        Ok(written)
+    }
+
+    /// Fetches a page of the AoC website, given as a path relative to the base
+    /// URL, using the session of `account`.
+    pub fn get(&self, account: &Account, path: &str) -> Result<String, RuntimeError> {
+        let response = self.http.get(path, &self.get_session(account)?)?;
+
+        if response.status != 200 && validate::is_logged_out_message(&response.body) {
+            Err(RuntimeError::SessionInvalid(account.clone()))
+        } else if response.status == 200 {
+            Ok(response.body)
+        } else {
+            Err(RuntimeError::HttpStatus {
+                status: response.status,
+                path: path.to_string(),
+            })
+        }
+    }
+
+    pub fn fetch(&self, account: &Account, challenge: &Challenge) -> Result<String, RuntimeError> {
+        let path = self.input_path(account, challenge);
+
+        if let Some(input) = self.read_cached(&path)? {
+            Ok(input)
+        } else {
+            let input = self
+                .get(
+                    account,
+                    &format!("{}/day/{}/input", challenge.year, challenge.day.0),
+                )
+                .map_err(|e| match e {
+                    RuntimeError::HttpStatus { status: 404, .. } => {
+                        RuntimeError::PuzzleLocked(*challenge)
+                    }
+                    e => e,
+                })?;
+
+            validate::check_input(&input).map_err(|reason| RuntimeError::InvalidInput {
+                challenge: *challenge,
+                reason,
+            })?;
+
+            std::fs::create_dir_all(self.cache_dir(account))?;
+            self.write_cached(&path, &input)?;
+
+            Ok(input)
+        }
+    }
+
+    pub fn leaderboard_path(&self, year: Year, id: u64) -> PathBuf {
+        self.cache_dir(&Account::Default)
+            .join(LEADERBOARDS_DIR)
+            .join(format!("{year}_{id}.json"))
+    }
+
+    /// Returns a private leaderboard, along with the time since it was
+    /// fetched. It is only fetched again once the cached one is older than
+    /// [`LEADERBOARD_REFRESH`].
+    pub fn fetch_leaderboard(
+        &self,
+        year: Year,
+        id: u64,
+    ) -> Result<(Leaderboard, Duration), RuntimeError> {
+        let path = self.leaderboard_path(year, id);
+        let age = std::fs::metadata(&path)
+            .and_then(|metadata| metadata.modified())
+            .ok()
+            .and_then(|modified| modified.elapsed().ok())
+            .filter(|age| *age < LEADERBOARD_REFRESH);
+
+        let json = if age.is_some() {
+            std::fs::read_to_string(&path)?
+        } else {
+            let json = self.get(
+                &Account::Default,
+                &format!("{year}/leaderboard/private/view/{id}.json"),
+            )?;
+
+            // Without access, the website redirects to an HTML page
+            if serde_json::from_str::<Leaderboard>(&json).is_err() {
+                return Err(RuntimeError::LeaderboardUnavailable(id));
+            }
+
+            std::fs::create_dir_all(path.parent().unwrap_or(&path))?;
+            std::fs::write(&path, &json)?;
+
+            json
+        };
+
+        Ok((serde_json::from_str(&json)?, age.unwrap_or_default()))
+    }
+
+    pub fn puzzle_path(&self, challenge: &Challenge) -> PathBuf {
+        self.cache_dir(&Account::Default)
+            .join(challenge.puzzle_name())
+    }
+
+    /// Returns the description of a puzzle if it is already cached.
+    pub fn cached_puzzle(&self, challenge: &Challenge) -> Result<Option<Puzzle>, RuntimeError> {
+        let path = self.puzzle_path(challenge);
+
+        if path.is_file() {
+            Ok(Some(Puzzle::from_html(&std::fs::read_to_string(path)?)))
+        } else {
+            Ok(None)
+        }
+    }
+
+    /// Fetches the description of a puzzle, which is cached alongside the
+    /// inputs of the default account.
+    pub fn fetch_puzzle(&self, challenge: &Challenge) -> Result<Puzzle, RuntimeError> {
+        let path = self.puzzle_path(challenge);
+
+        let html = if path.is_file() {
+            std::fs::read_to_string(path)?
+        } else {
+            let html = self.get(
+                &Account::Default,
+                &format!("{}/day/{}", challenge.year, challenge.day.0),
+            )?;
+
+            std::fs::create_dir_all(self.cache_dir(&Account::Default))?;
+            std::fs::write(path, &html)?;
+
+            html
+        };
+
+        Ok(Puzzle::from_html(&html))
+    }
+
+    /// Returns the name of the user that the session of an account belongs to,
+    /// if the session is valid.
+    pub fn check_session(&self, account: &Account) -> Result<Option<String>, RuntimeError> {
+        let response = self.http.get("settings", &self.get_session(account)?)?;
+
+        if response.status != 200 {
+            return Err(RuntimeError::HttpStatus {
+                status: response.status,
+                path: "settings".to_string(),
+            });
+        }
+
+        Ok(validate::logged_in_user(&response.body))
+    }
+
+    /// Returns the cached inputs of every account that don't look like puzzle
+    /// inputs, with the reason why.
+    pub fn check_integrity(&self) -> Result<Vec<(PathBuf, String)>, RuntimeError> {
+        let mut suspicious = Vec::new();
+
+        for account in self.get_accounts()? {
+            for challenge in self.get_present(&account)? {
+                let path = self.input_path(&account, &challenge);
+
+                // Encrypted inputs can only be checked once decrypted
+                if !path.is_file() {
+                    continue;
+                }
+
+                match std::fs::read_to_string(&path) {
+                    Ok(input) => {
+                        if let Err(reason) = validate::check_input(&input) {
+                            suspicious.push((path, reason));
+                        }
+                    }
+                    Err(e) if e.kind() == std::io::ErrorKind::InvalidData => {
+                        suspicious.push((path, "it isn't valid UTF-8".to_string()));
+                    }
+                    Err(e) => return Err(e.into()),
+                }
+            }
+        }
+
+        Ok(suspicious)
+    }
+
+    pub fn get_answers(
+        &self,
+        account: &Account,
+        challenge: &Challenge,
+    ) -> Result<Answers, RuntimeError> {
+        match self.read_cached(&self.answers_path(account, challenge))? {
+            Some(s) => Ok(Answers::from_table(&s.parse()?)),
+            None => Ok(Answers::default()),
+        }
+    }
+
+    pub fn guesses_path(&self, challenge: &Challenge) -> PathBuf {
+        self.cache_dir(&Account::Default)
+            .join(challenge.guesses_name())
+    }
+
+    /// Returns the answers of the default account that were rejected.
+    pub fn get_guesses(&self, challenge: &Challenge) -> Result<Guesses, RuntimeError> {
+        match self.read_cached(&self.guesses_path(challenge))? {
+            Some(s) => Ok(toml::from_str(&s)?),
+            None => Ok(Guesses::default()),
+        }
+    }
+
+    pub fn set_guesses(
+        &self,
+        challenge: &Challenge,
+        guesses: &Guesses,
+    ) -> Result<(), RuntimeError> {
+        std::fs::create_dir_all(self.cache_dir(&Account::Default))?;
+        self.write_cached(&self.guesses_path(challenge), &toml::to_string(guesses)?)
+    }
+
+    /// Submits the answer of a part of a challenge for the default account,
+    /// and returns the verdict of the website.
+    pub fn submit(
+        &self,
+        challenge: &Challenge,
+        part: u8,
+        answer: &str,
+    ) -> Result<Verdict, RuntimeError> {
+        let path = format!("{}/day/{}/answer", challenge.year, challenge.day.0);
+        let response = self.http.post_form(
+            &path,
+            &self.get_session(&Account::Default)?,
+            &[("level", &part.to_string()), ("answer", answer)],
+        )?;
+
+        if response.status == 200 {
+            Ok(Verdict::from_html(&response.body))
+        } else if validate::is_logged_out_message(&response.body) {
+            Err(RuntimeError::SessionInvalid(Account::Default))
+        } else {
+            Err(RuntimeError::HttpStatus {
+                status: response.status,
+                path,
+            })
+        }
+    }
+
+    pub fn run_path(&self, challenge: &Challenge) -> PathBuf {
+        self.cache_dir(&Account::Default).join(challenge.run_name())
+    }
+
+    pub fn get_run(&self, challenge: &Challenge) -> Result<RunRecord, RuntimeError> {
+        match std::fs::read_to_string(self.run_path(challenge)) {
+            Ok(s) => Ok(toml::from_str(&s)?),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(RunRecord::default()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    pub fn set_run(&self, challenge: &Challenge, run: &RunRecord) -> Result<(), RuntimeError> {
+        std::fs::create_dir_all(self.cache_dir(&Account::Default))?;
+        std::fs::write(self.run_path(challenge), toml::to_string(run)?)?;
+
+        Ok(())
+    }
+
+    pub fn set_answers(
+        &self,
+        account: &Account,
+        challenge: &Challenge,
+        answers: &Answers,
+    ) -> Result<(), RuntimeError> {
+        std::fs::create_dir_all(self.cache_dir(account))?;
+        self.write_cached(
+            &self.answers_path(account, challenge),
+            &answers.to_table().to_string(),
+        )
+    }
+}
@@ -0,0 +1,123 @@
+use std::collections::BTreeMap;
+
+use chrono::{DateTime, Datelike, FixedOffset, NaiveDate, Utc};
+use serde::Deserialize;
+
+use crate::{Challenge, Day, RuntimeError, Year};
+
+/// Number of days of the events, from the year of each entry until the next
+/// one: the events of 2015 to 2024 have 25 puzzles, and those from 2025 on
+/// have 12.
+const EVENT_DAYS: [(i32, u32); 2] = [(2015, 25), (2025, 12)];
+
+/// Offset of the timezone in which puzzles unlock, at midnight (UTC-5).
+const UNLOCK_OFFSET: i32 = -5 * 3600;
+
+/// The days of each Advent of Code event. The built-in table can be
+/// overridden in the `[calendar]` table of the configuration, where each entry
+/// gives the number of days of the events from its year on:
+///
+/// ```toml
+/// [calendar.days]
+/// 2030 = 10
+/// ```
+///
+/// On the last day of an event, the second part is free: its star is awarded
+/// once every other star of the event has been collected, so it has no answer.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Calendar {
+    pub days: BTreeMap<String, u32>,
+}
+
+impl Calendar {
+    /// Returns the number of days of the event of `year`.
+    pub fn days_in(&self, year: Year) -> u32 {
+        let overrides = self
+            .days
+            .iter()
+            .filter_map(|(year, days)| Some((year.parse::<i32>().ok()?, *days)));
+
+        EVENT_DAYS
+            .into_iter()
+            .chain(overrides)
+            .collect::<BTreeMap<_, _>>()
+            .range(..=year.0)
+            .next_back()
+            .map_or(Day::MAX, |(_, days)| *days)
+    }
+
+    /// Returns the days of the event of `year`, in order.
+    pub fn days(&self, year: Year) -> impl Iterator<Item = Day> {
+        (1..=self.days_in(year)).map(Day)
+    }
+
+    pub fn last_day(&self, year: Year) -> Day {
+        Day(self.days_in(year))
+    }
+
+    /// Whether the second part of the challenge is the free one of the last
+    /// day.
+    pub fn has_free_part_2(&self, challenge: &Challenge) -> bool {
+        challenge.day == self.last_day(challenge.year)
+    }
+
+    /// Returns the challenge if its day is part of the event of its year.
+    pub fn check_challenge(&self, challenge: Challenge) -> Result<Challenge, RuntimeError> {
+        let days = self.days_in(challenge.year);
+
+        if challenge.day.0 <= days {
+            Ok(challenge)
+        } else {
+            Err(RuntimeError::DayOutsideEvent { challenge, days })
+        }
+    }
+
+    /// Returns the date and time at which the puzzle of the challenge unlocks.
+    pub fn unlock_time(&self, challenge: &Challenge) -> DateTime<FixedOffset> {
+        NaiveDate::from_ymd_opt(challenge.year.0, 12, challenge.day.0)
+            .and_then(|date| date.and_hms_opt(0, 0, 0))
+            .and_then(|time| time.and_local_timezone(unlock_timezone()).single())
+            .expect("every AoC day should be a valid date in December")
+    }
+
+    /// Returns the latest unlocked challenge if an event is ongoing.
+    pub fn current_challenge(&self) -> Option<Challenge> {
+        let now = Utc::now().with_timezone(&unlock_timezone());
+        let year = Year::new(now.year()).ok()?;
+
+        if now.month() == 12 && now.day() <= self.days_in(year) {
+            Some(Challenge::new(Day(now.day()), year))
+        } else {
+            None
+        }
+    }
+
+    pub(crate) fn validate(&self) -> Result<(), String> {
+        for (year, days) in &self.days {
+            if year
+                .parse::<i32>()
+                .ok()
+                .and_then(|year| Year::new(year).ok())
+                .is_none()
+            {
+                return Err(format!(
+                    "the keys of `calendar.days` must be years from 2015 on, got \"{year}\""
+                ));
+            }
+
+            if !(1..=Day::MAX).contains(days) {
+                return Err(format!(
+                    "`calendar.days.{year}` must be between 1 and {}, got {days}",
+                    Day::MAX
+                ));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn unlock_timezone() -> FixedOffset {
+    FixedOffset::east_opt(UNLOCK_OFFSET).expect("the unlock offset should be valid")
+}
@@ -0,0 +1,136 @@
+use std::{fmt, str::FromStr};
+
+use chrono::{Datelike, Local};
+use serde::Deserialize;
+
+use crate::RuntimeError;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[repr(transparent)]
+pub struct Day(pub(crate) u32);
+
+impl Day {
+    /// Number of days of the longest events. The actual days of each event are
+    /// given by the [`Calendar`](crate::calendar::Calendar).
+    pub const MAX: u32 = 25;
+
+    pub fn new(value: u32) -> Result<Self, RuntimeError> {
+        if (1..=Self::MAX).contains(&value) {
+            Ok(Self(value))
+        } else {
+            Err(RuntimeError::DayOutsideRange)
+        }
+    }
+
+    pub fn get(self) -> u32 {
+        self.0
+    }
+}
+
+impl FromStr for Day {
+    type Err = RuntimeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let value: u32 = s.parse()?;
+
+        Self::new(value)
+    }
+}
+
+impl fmt::Display for Day {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:02}", self.0)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Deserialize)]
+#[serde(try_from = "i32")]
+#[repr(transparent)]
+pub struct Year(pub(crate) i32);
+
+impl Year {
+    pub fn new(value: i32) -> Result<Self, RuntimeError> {
+        if (2015..).contains(&value) {
+            Ok(Self(value))
+        } else {
+            Err(RuntimeError::YearOutsideRange(value))
+        }
+    }
+
+    pub fn current() -> Self {
+        Self(Local::now().year())
+    }
+
+    pub fn get(self) -> i32 {
+        self.0
+    }
+}
+
+impl TryFrom<i32> for Year {
+    type Error = RuntimeError;
+
+    fn try_from(value: i32) -> Result<Self, Self::Error> {
+        Self::new(value)
+    }
+}
+
+impl FromStr for Year {
+    type Err = RuntimeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let value: i32 = s.parse()?;
+
+        Self::new(value)
+    }
+}
+
+impl fmt::Display for Year {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Challenge {
+    pub day: Day,
+    pub year: Year,
+}
+
+impl Challenge {
+    pub fn new(day: Day, year: Year) -> Self {
+        Self { day, year }
+    }
+
+    pub fn input_name(&self) -> String {
+        format!("{}_{}.txt", self.year, self.day)
+    }
+
+    pub fn answers_name(&self) -> String {
+        format!("{}_{}.answers.toml", self.year, self.day)
+    }
+
+    pub fn puzzle_name(&self) -> String {
+        format!("{}_{}.puzzle.html", self.year, self.day)
+    }
+
+    pub fn guesses_name(&self) -> String {
+        format!("{}_{}.guesses.toml", self.year, self.day)
+    }
+
+    pub fn run_name(&self) -> String {
+        format!("{}_{}.run.toml", self.year, self.day)
+    }
+
+    pub fn from_input_name(s: &str) -> Result<Self, RuntimeError> {
+        s.strip_suffix(".txt")
+            .and_then(|s| s.split_once('_'))
+            .and_then(|(year, day)| Some(Self::new(day.parse().ok()?, year.parse().ok()?)))
+            .ok_or_else(|| RuntimeError::InvalidInputName(s.to_string()))
+    }
+}
+
+impl fmt::Display for Challenge {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} ({})", self.day, self.year)
+    }
+}
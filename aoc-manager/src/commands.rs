@@ -0,0 +1,550 @@
+use std::collections::HashSet;
+
+use colored::Colorize;
+
+use crate::{
+    config::{Config, Naming},
+    leaderboard, migrate,
+    runner::{measure, run_solution},
+    status::{self, DayStatus},
+    template::{Dependency, Placeholders, Template},
+    Account, Challenge, Day, DayCrate, InputCache, RuntimeError, Workspace, Year,
+    LEADERBOARD_REFRESH,
+};
+
+/// Shows the progress on the events of the given years, or of every year with
+/// a day in the workspace. With `run`, the tests and solutions of the days are
+/// run first.
+pub fn status(
+    years: Vec<Year>,
+    run: bool,
+    json: bool,
+    config: &Config,
+) -> Result<(), RuntimeError> {
+    let members = Workspace::open(&config.root)?
+        .get_days(config)
+        .into_iter()
+        .collect::<HashSet<_>>();
+
+    let mut years = if years.is_empty() {
+        members.iter().map(|challenge| challenge.year).collect()
+    } else {
+        years
+    };
+    years.sort();
+    years.dedup();
+
+    if years.is_empty() {
+        years.push(Year::current());
+    }
+
+    if run {
+        let mut challenges = members
+            .iter()
+            .filter(|challenge| years.contains(&challenge.year))
+            .collect::<Vec<_>>();
+        challenges.sort();
+
+        for challenge in challenges {
+            eprintln!("{} {challenge}", "running".dimmed());
+
+            // A failing day shouldn't hide the status of the others
+            if let Err(e) = measure(*challenge, config) {
+                eprintln!("{} {e}", "warning:".yellow().bold());
+            }
+        }
+    }
+
+    let calendars = years
+        .iter()
+        .map(|year| {
+            let days = config
+                .calendar
+                .days(*year)
+                .map(|day| DayStatus::new(Challenge::new(day, *year), &members, config))
+                .collect::<Result<Vec<_>, _>>()?;
+
+            Ok((*year, days))
+        })
+        .collect::<Result<Vec<_>, RuntimeError>>()?;
+
+    if json {
+        let days = calendars
+            .iter()
+            .flat_map(|(_, days)| days)
+            .collect::<Vec<_>>();
+
+        println!("{}", serde_json::to_string_pretty(&days)?);
+    } else {
+        for (i, (year, days)) in calendars.iter().enumerate() {
+            if i > 0 {
+                println!();
+            }
+            status::render(*year, days);
+        }
+    }
+
+    Ok(())
+}
+
+/// Runs a challenge against the inputs of every known account, and compares
+/// the results with the answers recorded for each account. With `record`,
+/// answers are recorded for the parts that don't have one yet.
+pub fn check(challenge: Challenge, record: bool, config: &Config) -> Result<(), RuntimeError> {
+    let cache = InputCache::new(config);
+    let mut mismatches = 0;
+
+    for account in cache.get_accounts()? {
+        let Some(input) = cache.cached_input(&account, &challenge)? else {
+            println!("{account}: {}", "no input cached".yellow());
+            continue;
+        };
+
+        let (answers, runtime) = run_solution(challenge, &input, config)?;
+        let mut recorded = cache.get_answers(&account, &challenge)?;
+
+        if account == Account::Default {
+            let mut run = cache.get_run(&challenge)?;
+            run.runtime_ms = Some(runtime.as_millis() as u64);
+            cache.set_run(&challenge, &run)?;
+        }
+
+        println!("{account}:");
+        for (part, answer, recorded) in [
+            (1, &answers.part_1, &mut recorded.part_1),
+            (2, &answers.part_2, &mut recorded.part_2),
+        ] {
+            if part == 2 && config.calendar.has_free_part_2(&challenge) {
+                println!("  part 2: {}", "free on the last day".dimmed());
+                continue;
+            }
+
+            match (answer, &recorded) {
+                (None, _) => println!("  part {part}: {}", "no answer".dimmed()),
+                (Some(answer), None) => {
+                    println!(
+                        "  part {part}: {answer} {}",
+                        "(no recorded answer)".yellow()
+                    );
+                    if record {
+                        *recorded = Some(answer.clone());
+                    }
+                }
+                (Some(answer), Some(expected)) if answer == expected => {
+                    println!("  part {part}: {}", answer.green())
+                }
+                (Some(answer), Some(expected)) => {
+                    mismatches += 1;
+                    println!(
+                        "  part {part}: {} (expected {expected})",
+                        answer.as_str().red()
+                    );
+                }
+            }
+        }
+
+        if record {
+            cache.set_answers(&account, &challenge, &recorded)?;
+        }
+    }
+
+    if mismatches == 0 {
+        Ok(())
+    } else {
+        Err(RuntimeError::AnswerMismatch(mismatches))
+    }
+}
+
+/// Creates the crate of a day from a template, and adds it to the workspace.
+///
+/// Everything that can fail is done before the workspace is touched: the
+/// puzzle and its input are fetched first, and the crate is built in a
+/// staging directory that is only moved in place once complete. If anything
+/// fails, the staging directory is removed and the workspace is left as it
+/// was. The fetched input stays in the cache, so that a retry doesn't fetch it
+/// again.
+///
+/// In offline mode, nothing is fetched: the crate is created without an input
+/// (and with empty title and example, unless the puzzle is already cached),
+/// and is marked so that `fetch` gets its input later.
+pub fn add(
+    challenge: Challenge,
+    template: &Template,
+    dependencies: &[Dependency],
+    offline: bool,
+    config: &Config,
+) -> Result<(), RuntimeError> {
+    let cache = InputCache::new(config);
+    let crate_name = config.naming.crate_name(&challenge);
+    let crate_dir = config.root.join(&crate_name);
+
+    if crate_dir.exists() {
+        return Err(RuntimeError::CrateDirectoryExists(crate_dir));
+    }
+
+    let mut workspace = Workspace::open(&config.root)?;
+    workspace.add_day(challenge, config)?;
+
+    let puzzle = if offline {
+        cache.cached_puzzle(&challenge)?.unwrap_or_default()
+    } else {
+        let puzzle = cache.fetch_puzzle(&challenge)?;
+        cache.fetch(&Account::Default, &challenge)?;
+        puzzle
+    };
+
+    let staging_dir = config.root.join(format!(".{crate_name}.staging"));
+    if staging_dir.exists() {
+        // Left over by an interrupted add
+        std::fs::remove_dir_all(&staging_dir)?;
+    }
+
+    let stage = || -> Result<(), RuntimeError> {
+        template.instantiate(
+            &staging_dir,
+            &Placeholders {
+                crate_name: crate_name.clone(),
+                challenge,
+                puzzle,
+            },
+        )?;
+
+        let mut day_crate = DayCrate::open(staging_dir.clone(), crate_name.clone())?;
+        day_crate.set_name();
+        for dependency in dependencies {
+            day_crate.add_dependency(dependency);
+        }
+        day_crate.set_pending_fetch(offline);
+        day_crate.write()?;
+
+        Ok(())
+    };
+
+    if let Err(e) = stage() {
+        let _ = std::fs::remove_dir_all(&staging_dir);
+        return Err(e);
+    }
+
+    std::fs::rename(&staging_dir, &crate_dir)?;
+
+    if let Err(e) = workspace.write() {
+        let _ = std::fs::remove_dir_all(&crate_dir);
+        return Err(e);
+    }
+
+    Ok(())
+}
+
+/// Shows the standings of a private leaderboard, or the times of its members
+/// on a single day.
+pub fn leaderboard(
+    id: u64,
+    year: Year,
+    day: Option<Day>,
+    config: &Config,
+) -> Result<(), RuntimeError> {
+    let (leaderboard, age) = InputCache::new(config).fetch_leaderboard(year, id)?;
+
+    println!(
+        "{} {}",
+        format!("Private leaderboard #{} ({year})", leaderboard.owner_id).bold(),
+        if age.is_zero() {
+            "(just fetched)".dimmed()
+        } else {
+            format!(
+                "(fetched {} ago, can be refreshed in {})",
+                leaderboard::format_duration(age.as_secs() as i64),
+                leaderboard::format_duration((LEADERBOARD_REFRESH - age).as_secs() as i64),
+            )
+            .dimmed()
+        }
+    );
+
+    match day {
+        Some(day) => leaderboard.render_day(
+            config.calendar.check_challenge(Challenge::new(day, year))?,
+            &config.calendar,
+        ),
+        None => leaderboard.render(year, &config.calendar),
+    }
+
+    Ok(())
+}
+
+/// Asks a yes/no question on the terminal, and returns whether the answer is
+/// yes.
+pub fn confirm(question: &str) -> Result<bool, RuntimeError> {
+    print!("{question} [y/N] ");
+    std::io::Write::flush(&mut std::io::stdout())?;
+
+    let mut answer = String::new();
+    std::io::stdin().read_line(&mut answer)?;
+
+    Ok(matches!(answer.trim(), "y" | "Y" | "yes"))
+}
+
+/// Removes the crate of a day from the workspace, and deletes its directory or
+/// moves it to the archive directory. Its cached input and answers are kept.
+pub fn remove(challenge: Challenge, archive: bool, config: &Config) -> Result<(), RuntimeError> {
+    let crate_name = config.naming.crate_name(&challenge);
+    let crate_dir = config.root.join(&crate_name);
+
+    let mut workspace = Workspace::open(&config.root)?;
+    workspace.remove(&crate_name)?;
+
+    if archive {
+        let archive_dir = config.paths.archive.join(&crate_name);
+
+        if archive_dir.exists() {
+            return Err(RuntimeError::CrateDirectoryExists(archive_dir));
+        }
+
+        std::fs::create_dir_all(&config.paths.archive)?;
+        std::fs::rename(&crate_dir, &archive_dir)?;
+
+        if let Err(e) = workspace.write() {
+            let _ = std::fs::rename(&archive_dir, &crate_dir);
+            return Err(e);
+        }
+    } else {
+        workspace.write()?;
+
+        if crate_dir.exists() {
+            std::fs::remove_dir_all(&crate_dir)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Renames the day crates named after the `from` pattern to the configured
+/// naming pattern: their directory, their package and their workspace member.
+pub fn rename_crates(from: &str, dry_run: bool, config: &Config) -> Result<(), RuntimeError> {
+    let from = Naming {
+        crate_name: from.to_string(),
+    };
+    from.validate().map_err(RuntimeError::InvalidNaming)?;
+
+    let mut workspace = Workspace::open(&config.root)?;
+    let renames = workspace
+        .members()
+        .iter()
+        .filter_map(|member| {
+            let name = member.as_str()?;
+            let challenge = from.parse_crate_name(name).ok()?;
+
+            Some((name.to_string(), config.naming.crate_name(&challenge)))
+        })
+        .filter(|(old_name, new_name)| old_name != new_name)
+        .collect::<Vec<_>>();
+
+    for (old_name, new_name) in renames {
+        println!("{old_name} -> {new_name}");
+
+        if dry_run {
+            continue;
+        }
+
+        let (old_dir, new_dir) = (config.root.join(&old_name), config.root.join(&new_name));
+
+        if new_dir.exists() {
+            return Err(RuntimeError::CrateDirectoryExists(new_dir));
+        }
+
+        workspace.rename(&old_name, &new_name)?;
+        std::fs::rename(&old_dir, &new_dir)?;
+
+        let mut day_crate = DayCrate::open(new_dir, new_name)?;
+        day_crate.set_name();
+        day_crate.write()?;
+
+        // Written after each crate, so that the workspace matches the
+        // directories even if a later rename fails
+        workspace.write()?;
+        workspace = Workspace::open(&config.root)?;
+    }
+
+    Ok(())
+}
+
+/// Rewrites the given day crates to the current layout of the templates (see
+/// [`migrate::migrate_source`]), leaving their solution code untouched. An
+/// input left in the `src` directory by the oldest layout is moved to the
+/// input cache.
+pub fn migrate(
+    challenges: &[Challenge],
+    dry_run: bool,
+    config: &Config,
+) -> Result<(), RuntimeError> {
+    let cache = InputCache::new(config);
+    let mut failures = 0;
+
+    for challenge in challenges {
+        let crate_dir = config.root.join(config.naming.crate_name(challenge));
+        let main_path = crate_dir.join("src").join("main.rs");
+
+        match migrate::migrate_source(&std::fs::read_to_string(&main_path)?) {
+            Ok(None) => println!("{challenge}: {}", "up to date".dimmed()),
+            Ok(Some(migrated)) => {
+                println!("{challenge}: {}", "migrated".green());
+
+                if !dry_run {
+                    std::fs::write(&main_path, migrated)?;
+                }
+            }
+            Err(reason) => {
+                failures += 1;
+                println!(
+                    "{challenge}: {}",
+                    format!("can't be migrated automatically, as {reason}").yellow()
+                );
+            }
+        }
+
+        let old_input = crate_dir.join("src").join("input.txt");
+        let input = cache.input_path(&Account::Default, challenge);
+
+        if old_input.is_file() && !cache.has_input(&Account::Default, challenge) {
+            println!("{challenge}: {}", "input moved to the cache".green());
+
+            if !dry_run {
+                std::fs::create_dir_all(cache.cache_dir(&Account::Default))?;
+                std::fs::rename(&old_input, &input)?;
+            }
+        }
+    }
+
+    if failures == 0 {
+        Ok(())
+    } else {
+        Err(RuntimeError::MigrationFailed(failures))
+    }
+}
+
+/// Returns the challenge designated by the command line arguments. When no
+/// day is given, the latest unlocked day is used if AoC is ongoing. When only
+/// the day is given, the year defaults to the configured one, or the current
+/// one.
+pub fn resolve_challenge(
+    day: Option<Day>,
+    year: Option<Year>,
+    config: &Config,
+) -> Result<Challenge, RuntimeError> {
+    match (day, year) {
+        (None, Some(_)) => Err(RuntimeError::YearWithoutDay),
+        (None, None) => config
+            .calendar
+            .current_challenge()
+            .ok_or(RuntimeError::NoDay),
+        (Some(day), year) => config.calendar.check_challenge(Challenge::new(
+            day,
+            year.or(config.default_year).unwrap_or_else(Year::current),
+        )),
+    }
+}
+
+/// Fetches the inputs of every day of the workspace that are missing from the
+/// cache, for the default account or for every account.
+pub fn fetch(all_accounts: bool, config: &Config) -> Result<(), RuntimeError> {
+    let cache = InputCache::new(config);
+    let crates = Workspace::open(&config.root)?
+        .get_days(config)
+        .into_iter()
+        .collect::<HashSet<_>>();
+
+    let accounts = if all_accounts {
+        cache.get_accounts()?
+    } else {
+        vec![Account::Default]
+    };
+
+    for account in accounts {
+        let inputs = cache
+            .get_present(&account)?
+            .into_iter()
+            .collect::<HashSet<_>>();
+
+        for challenge in crates.difference(&inputs) {
+            cache.fetch(&account, challenge)?;
+        }
+    }
+
+    // Every crate has its input now, so clear the marks left by offline adds
+    for challenge in &crates {
+        let mut day_crate = DayCrate::new(*challenge, config)?;
+
+        if day_crate.is_pending_fetch() {
+            day_crate.set_pending_fetch(false);
+            day_crate.write()?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Checks the session of the default account, or of every account.
+pub fn check_sessions(all_accounts: bool, config: &Config) -> Result<(), RuntimeError> {
+    let cache = InputCache::new(config);
+    let accounts = if all_accounts {
+        cache.get_accounts()?
+    } else {
+        vec![Account::Default]
+    };
+    let mut invalid = 0;
+
+    for account in accounts {
+        match cache.check_session(&account)? {
+            Some(user) => println!("{account}: {} ({user})", "valid".green()),
+            None => {
+                invalid += 1;
+                println!("{account}: {}", "invalid or expired".red());
+            }
+        }
+    }
+
+    if invalid == 0 {
+        Ok(())
+    } else {
+        Err(RuntimeError::InvalidSessions(invalid))
+    }
+}
+
+/// Reports the cached inputs that don't look like puzzle inputs, and deletes
+/// them with `delete`.
+pub fn check_cache(delete: bool, config: &Config) -> Result<(), RuntimeError> {
+    let suspicious = InputCache::new(config).check_integrity()?;
+
+    for (path, reason) in &suspicious {
+        println!("{}: {}", path.display(), reason.yellow());
+
+        if delete {
+            std::fs::remove_file(path)?;
+        }
+    }
+
+    if suspicious.is_empty() {
+        println!("{}", "every cached input looks valid".green());
+        Ok(())
+    } else if delete {
+        println!("{} cached input(s) removed.", suspicious.len());
+        Ok(())
+    } else {
+        Err(RuntimeError::SuspiciousCache(suspicious.len()))
+    }
+}
+
+/// Writes the encrypted copies of the cached inputs and answers.
+pub fn encrypt_cache(config: &Config) -> Result<(), RuntimeError> {
+    let written = InputCache::new(config).encrypt_all()?;
+
+    println!("{written} encrypted file(s) written.");
+    Ok(())
+}
+
+/// Restores the cached inputs and answers from their encrypted copies.
+pub fn decrypt_cache(overwrite: bool, config: &Config) -> Result<(), RuntimeError> {
+    let written = InputCache::new(config).decrypt_all(overwrite)?;
+
+    println!("{written} file(s) decrypted.");
+    Ok(())
+}
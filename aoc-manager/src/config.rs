@@ -0,0 +1,319 @@
+use std::path::{Path, PathBuf};
+
+use aoc_utils::{config_files, format_crate_name, parse_crate_name, DEFAULT_CRATE_NAME};
+use serde::Deserialize;
+use toml::{Table, Value};
+
+use crate::{calendar::Calendar, Challenge, Day, RuntimeError, Year};
+
+/// The configuration of `aoc-manager`, read from the `aoc.toml` file at the
+/// root of the workspace and from the user's `aoc.toml`, which overrides it.
+/// Every key is optional:
+///
+/// ```toml
+/// default_year = 2023
+///
+/// [paths]
+/// cache = ".input-cache"
+/// session = ".aoc-token"
+/// sessions = ".aoc-sessions.toml"
+/// templates = "templates"
+/// archive = "archive"
+/// cache_key = ".aoc-cache-key"
+///
+/// [cache]
+/// encrypt = false
+///
+/// [naming]
+/// crate = "day_{year}_{day}"
+///
+/// [add]
+/// template = "default"
+///
+/// [http]
+/// base_url = "https://adventofcode.com"
+/// user_agent = "github.com/me/aoc by me@example.com"
+/// throttle_ms = 1000
+/// retries = 3
+/// backoff_ms = 1000
+///
+/// [watch]
+/// release = false
+/// clear = false
+/// run_on_start = false
+///
+/// [calendar.days]
+/// 2025 = 12
+/// ```
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    /// Root directory of the workspace, which the paths are relative to.
+    #[serde(skip)]
+    pub root: PathBuf,
+    /// Year used when a day is given without a year. Defaults to the current
+    /// year.
+    pub default_year: Option<Year>,
+    pub paths: Paths,
+    pub cache: Cache,
+    pub naming: Naming,
+    pub add: Add,
+    pub http: Http,
+    pub watch: Watch,
+    pub calendar: Calendar,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Paths {
+    /// Directory in which inputs and answers are cached.
+    pub cache: PathBuf,
+    /// File containing the session of the default account.
+    pub session: PathBuf,
+    /// File containing the sessions of the named accounts.
+    pub sessions: PathBuf,
+    /// Directory containing the templates of the day crates.
+    pub templates: PathBuf,
+    /// Directory into which `remove --archive` moves the day crates.
+    pub archive: PathBuf,
+    /// File containing the key of the encrypted cache, unless it is given by
+    /// the `AOC_CACHE_KEY` environment variable.
+    pub cache_key: PathBuf,
+}
+
+impl Default for Paths {
+    fn default() -> Self {
+        Self {
+            cache: aoc_utils::DEFAULT_CACHE_PATH.into(),
+            session: ".aoc-token".into(),
+            sessions: ".aoc-sessions.toml".into(),
+            templates: "templates".into(),
+            archive: "archive".into(),
+            cache_key: aoc_utils::DEFAULT_CACHE_KEY_PATH.into(),
+        }
+    }
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Cache {
+    /// Also write an encrypted copy of every input and answers file written to
+    /// the cache, which can be committed.
+    pub encrypt: bool,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Naming {
+    /// Pattern of the names of the day crates, in which `{year}` and `{day}`
+    /// are placeholders.
+    #[serde(rename = "crate")]
+    pub crate_name: String,
+}
+
+impl Default for Naming {
+    fn default() -> Self {
+        Self {
+            crate_name: DEFAULT_CRATE_NAME.to_string(),
+        }
+    }
+}
+
+impl Naming {
+    pub fn crate_name(&self, challenge: &Challenge) -> String {
+        format_crate_name(&self.crate_name, challenge.year.0, challenge.day.0)
+    }
+
+    pub fn parse_crate_name(&self, name: &str) -> Result<Challenge, RuntimeError> {
+        parse_crate_name(&self.crate_name, name)
+            .and_then(|(year, day)| Some(Challenge::new(Day::new(day).ok()?, Year(year))))
+            .ok_or_else(|| RuntimeError::InvalidCrateFormat(name.to_string()))
+    }
+
+    pub(crate) fn validate(&self) -> Result<(), String> {
+        let pattern = &self.crate_name;
+
+        for placeholder in ["{year}", "{day}"] {
+            if pattern.matches(placeholder).count() != 1 {
+                return Err(format!(
+                    "`naming.crate` must contain {placeholder} exactly once, got \"{pattern}\""
+                ));
+            }
+        }
+
+        let literals = pattern.replace("{year}", "").replace("{day}", "");
+        if let Some(c) = literals
+            .chars()
+            .find(|c| !(c.is_ascii_alphanumeric() || *c == '_' || *c == '-'))
+        {
+            return Err(format!(
+                "`naming.crate` can only contain letters, digits, '_' and '-' besides its placeholders, found '{c}'"
+            ));
+        }
+
+        let example = Challenge::new(Day(1), Year(2015));
+        if self.parse_crate_name(&self.crate_name(&example)).ok() != Some(example) {
+            return Err(format!(
+                "`naming.crate` must separate {{year}} and {{day}} with text that doesn't start with a digit, got \"{pattern}\""
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Add {
+    /// Preferred template, as a directory inside `paths.templates`.
+    pub template: String,
+}
+
+impl Default for Add {
+    fn default() -> Self {
+        Self {
+            template: "default".to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Http {
+    /// URL of the Advent of Code website.
+    pub base_url: String,
+    /// Value of the User-Agent header sent with every request, which should
+    /// let the AoC maintainers contact you.
+    pub user_agent: Option<String>,
+    /// Minimum time between two requests, in milliseconds.
+    pub throttle_ms: u64,
+    /// Number of times a request is retried after a network error or a server
+    /// error.
+    pub retries: u32,
+    /// Wait before the first retry, in milliseconds. It doubles at each retry,
+    /// unless the server asks for a longer one.
+    pub backoff_ms: u64,
+}
+
+impl Default for Http {
+    fn default() -> Self {
+        Self {
+            base_url: "https://adventofcode.com".to_string(),
+            user_agent: None,
+            throttle_ms: 1000,
+            retries: 3,
+            backoff_ms: 1000,
+        }
+    }
+}
+
+impl Http {
+    fn validate(&self) -> Result<(), String> {
+        if !(self.base_url.starts_with("https://") || self.base_url.starts_with("http://")) {
+            Err(format!(
+                "`http.base_url` must be an http(s) URL, got \"{}\"",
+                self.base_url
+            ))
+        } else if self
+            .user_agent
+            .as_deref()
+            .is_some_and(|s| s.trim().is_empty())
+        {
+            Err("`http.user_agent` must not be empty".to_string())
+        } else {
+            Ok(())
+        }
+    }
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Watch {
+    /// Build the solution in release mode.
+    pub release: bool,
+    /// Clear the terminal before each run.
+    pub clear: bool,
+    /// Run the solution once when the watch begins, without waiting for a
+    /// change.
+    pub run_on_start: bool,
+}
+
+impl Config {
+    /// Loads the configuration of the workspace at `root`. Each file is
+    /// validated on its own, so that errors point to the right file, then the
+    /// user's configuration is merged over the workspace's.
+    pub fn load(root: &Path) -> Result<Self, RuntimeError> {
+        let merged = config_files(root)
+            .into_iter()
+            .filter(|path| path.is_file())
+            .try_fold(Table::new(), |mut merged, path| {
+                let invalid = |message: String| RuntimeError::InvalidConfig {
+                    path: path.clone(),
+                    message,
+                };
+
+                let table = std::fs::read_to_string(&path)?
+                    .parse::<Table>()
+                    .map_err(|e| invalid(e.message().to_string()))?;
+
+                Self::from_table(table.clone()).map_err(invalid)?;
+                merge(&mut merged, table);
+
+                Ok::<_, RuntimeError>(merged)
+            })?;
+
+        let config = Self::from_table(merged).map_err(|message| RuntimeError::InvalidConfig {
+            path: root.join(aoc_utils::CONFIG_FILE),
+            message,
+        })?;
+
+        Ok(config.with_root(root))
+    }
+
+    /// Sets the root of the workspace, and makes the paths relative to it.
+    /// This is done by [`Config::load`], and is useful with configurations
+    /// that aren't read from files.
+    pub fn with_root(mut self, root: &Path) -> Self {
+        for path in [
+            &mut self.paths.cache,
+            &mut self.paths.session,
+            &mut self.paths.sessions,
+            &mut self.paths.templates,
+            &mut self.paths.archive,
+            &mut self.paths.cache_key,
+        ] {
+            *path = root.join(&*path);
+        }
+        self.root = root.to_path_buf();
+
+        self
+    }
+
+    fn from_table(table: Table) -> Result<Self, String> {
+        let config = Value::Table(table)
+            .try_into::<Self>()
+            .map_err(|e| e.message().to_string())?;
+
+        config.naming.validate()?;
+        config.http.validate()?;
+        config.calendar.validate()?;
+
+        Ok(config)
+    }
+
+    pub fn template_dir(&self, name: &str) -> PathBuf {
+        self.paths.templates.join(name)
+    }
+}
+
+/// Merges `overrides` into `base`, recursing into the tables present in both.
+fn merge(base: &mut Table, overrides: Table) {
+    for (key, value) in overrides {
+        match (base.get_mut(&key), value) {
+            (Some(Value::Table(base)), Value::Table(overrides)) => merge(base, overrides),
+            (_, value) => {
+                base.insert(key, value);
+            }
+        }
+    }
+}
@@ -0,0 +1,100 @@
+use std::path::PathBuf;
+
+use thiserror::Error;
+
+use crate::{Account, Challenge};
+
+#[derive(Debug, Error)]
+pub enum RuntimeError {
+    #[error("Integer parse error")]
+    IntegerParse(#[from] std::num::ParseIntError),
+    #[error("day value outside of valid range (1..=25)")]
+    DayOutsideRange,
+    #[error("day {} is not part of the {} event, which only has {} days.", .challenge.day, .challenge.year, .days)]
+    DayOutsideEvent { challenge: Challenge, days: u32 },
+    #[error("current date outside of AoC day range and no day specified (use the -d flag!)")]
+    NoDay,
+    #[error("i/o error")]
+    Io(#[from] std::io::Error),
+    #[error("toml parse error")]
+    TomlParse(#[from] toml::de::Error),
+    #[error("toml parse error")]
+    TomlEditParse(#[from] toml_edit::TomlError),
+    #[error("toml serialization error")]
+    TomlSerialize(#[from] toml::ser::Error),
+    #[error("json serialization error")]
+    Json(#[from] serde_json::Error),
+    #[error("the Cargo.toml file in the current directory is missing the [workspace] table (are you in the root directory?)")]
+    MissingWorkspace,
+    #[error("day {} is already present in the workspace", .0)]
+    DayAlreadyPresent(Challenge),
+    #[error("\"{}\" is not a member of the workspace", .0)]
+    MemberNotPresent(String),
+    #[error("\"{}\" is already a member of the workspace", .0)]
+    MemberAlreadyPresent(String),
+    #[error("the Cargo.toml file of {} is missing the [package] table", .0)]
+    MissingPackage(String),
+    #[error("the directory {} already exists. remove it, or add the crate to the workspace's members if it is complete.", .0.display())]
+    CrateDirectoryExists(PathBuf),
+    #[error("network error")]
+    NetworkError(#[from] Box<ureq::Error>),
+    #[error("the AoC website answered /{} with status {}.", .path, .status)]
+    HttpStatus { status: u16, path: String },
+    #[error("the AoC session of the {} account is invalid or expired. log in to the AoC website again, and update the session.", .0)]
+    SessionInvalid(Account),
+    #[error("{} session(s) are invalid or expired.", .0)]
+    InvalidSessions(usize),
+    #[error("the puzzle of day {} isn't unlocked yet.", .0)]
+    PuzzleLocked(Challenge),
+    #[error("the AoC website didn't send an input for day {}, as {}.", .challenge, .reason)]
+    InvalidInput {
+        challenge: Challenge,
+        reason: String,
+    },
+    #[error("the key of the encrypted cache wasn't found in the AOC_CACHE_KEY environment variable or in {}.", .0.display())]
+    MissingCacheKey(PathBuf),
+    #[error("the encrypted cache file {} can't be decrypted", .path.display())]
+    Decryption {
+        path: PathBuf,
+        #[source]
+        source: aoc_utils::DecryptionError,
+    },
+    #[error("{} cached input(s) look invalid (run `cache check --delete` to remove them).", .0)]
+    SuspiciousCache(usize),
+    #[error("leaderboard {} isn't available. check that your session is valid with `check-session`, and that you are a member of the leaderboard.", .0)]
+    LeaderboardUnavailable(u64),
+    #[error(
+        "AoC session key not found in AOC_SESSION environment variable or in the session file (.aoc-token by default)."
+    )]
+    MissingSessionKey,
+    #[error("notify error")]
+    WatchError(#[from] notify::Error),
+    #[error("the year '{}' is earlier than 2015, the earliest Advent of Code event.", .0)]
+    YearOutsideRange(i32),
+    #[error("year specified without day. specify either both, neither or just the day.")]
+    YearWithoutDay,
+    #[error("\"{}\" is not a valid AoC crate name.", .0)]
+    InvalidCrateFormat(String),
+    #[error("\"{}\" is not a valid AoC input file name.", .0)]
+    InvalidInputName(String),
+    #[error("the sessions file (.aoc-sessions.toml by default) must contain a [sessions] table mapping account names to session strings.")]
+    InvalidSessionsFile,
+    #[error("\"{}\" is not a valid account name in the sessions file (.aoc-sessions.toml by default). use only letters, digits, '-' and '_', and not \"default\" or \"leaderboards\".", .0)]
+    InvalidAccountName(String),
+    #[error("account \"{}\" not found in the sessions file (.aoc-sessions.toml by default).", .0)]
+    UnknownAccount(String),
+    #[error("the solution of day {} failed to run.", .0)]
+    SolutionFailed(Challenge),
+    #[error("{} answer(s) differ from the recorded ones.", .0)]
+    AnswerMismatch(usize),
+    #[error("invalid configuration in {}: {}", .path.display(), .message)]
+    InvalidConfig { path: PathBuf, message: String },
+    #[error("template \"{}\" not found. available templates: {}", .name, .available)]
+    UnknownTemplate { name: String, available: String },
+    #[error("\"{}\" is not a valid dependency, expected `name` or `name@version`.", .0)]
+    InvalidDependency(String),
+    #[error("{} day crate(s) couldn't be migrated.", .0)]
+    MigrationFailed(usize),
+    #[error("invalid naming pattern: {}", .0)]
+    InvalidNaming(String),
+}
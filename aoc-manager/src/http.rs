@@ -0,0 +1,234 @@
+use std::{
+    sync::Mutex,
+    thread,
+    time::{Duration, Instant},
+};
+
+use chrono::{DateTime, Utc};
+
+use crate::{config::Http, RuntimeError};
+
+/// User-Agent sent when none is configured. Setting `http.user_agent` to
+/// something that lets the AoC maintainers contact you is preferred.
+const DEFAULT_USER_AGENT: &str = concat!(
+    "aoc-manager/",
+    env!("CARGO_PKG_VERSION"),
+    " (no contact configured, see http.user_agent)"
+);
+
+/// Longest wait before a retry, whatever the server asks for.
+const MAX_RETRY_DELAY: Duration = Duration::from_secs(5 * 60);
+
+/// The moment of the last request to the AoC website, shared by every client
+/// of the process so that commands building their own client still wait the
+/// throttle delay between their requests.
+static LAST_REQUEST: Mutex<Option<Instant>> = Mutex::new(None);
+
+/// A request, as handed to a [`Transport`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Request {
+    pub method: &'static str,
+    pub url: String,
+    pub headers: Vec<(&'static str, String)>,
+    /// Form fields, sent URL-encoded in the body.
+    pub form: Vec<(String, String)>,
+}
+
+/// A response, whatever its status code.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Response {
+    pub status: u16,
+    pub body: String,
+    /// The delay asked for by the `Retry-After` header, if any.
+    pub retry_after: Option<Duration>,
+}
+
+impl Response {
+    fn is_transient_failure(&self) -> bool {
+        self.status == 429 || self.status >= 500
+    }
+}
+
+/// Sends requests over the network. Errors are only for requests that didn't
+/// get a response: error status codes are returned as responses.
+///
+/// The default transport uses `ureq`; tests can replace it to answer requests
+/// without a network.
+pub trait Transport {
+    fn send(&self, request: &Request) -> Result<Response, RuntimeError>;
+}
+
+#[derive(Debug, Default)]
+pub struct UreqTransport;
+
+impl Transport for UreqTransport {
+    fn send(&self, request: &Request) -> Result<Response, RuntimeError> {
+        let mut ureq_request = ureq::request(request.method, &request.url);
+        for (name, value) in &request.headers {
+            ureq_request = ureq_request.set(name, value);
+        }
+
+        let result = if request.form.is_empty() {
+            ureq_request.call()
+        } else {
+            let form = request
+                .form
+                .iter()
+                .map(|(name, value)| (name.as_str(), value.as_str()))
+                .collect::<Vec<_>>();
+
+            ureq_request.send_form(&form)
+        };
+
+        let response = match result {
+            Ok(response) | Err(ureq::Error::Status(_, response)) => response,
+            Err(e) => return Err(Box::new(e).into()),
+        };
+
+        let retry_after = response
+            .header("Retry-After")
+            .and_then(|value| parse_retry_after(value, Utc::now()));
+
+        Ok(Response {
+            status: response.status(),
+            retry_after,
+            body: response.into_string()?,
+        })
+    }
+}
+
+/// Returns the delay that a `Retry-After` header asks for, which is either a
+/// number of seconds or an HTTP date. A date in the past means no delay.
+pub fn parse_retry_after(value: &str, now: DateTime<Utc>) -> Option<Duration> {
+    let value = value.trim();
+
+    if let Ok(seconds) = value.parse() {
+        return Some(Duration::from_secs(seconds));
+    }
+
+    let date = DateTime::parse_from_rfc2822(value).ok()?;
+
+    Some(
+        (date.with_timezone(&Utc) - now)
+            .to_std()
+            .unwrap_or_default(),
+    )
+}
+
+/// The HTTP client shared by every request to the AoC website. It identifies
+/// itself with the configured User-Agent, waits `http.throttle_ms` between two
+/// requests, and retries the requests that failed because of the network or of
+/// the server (status 429 or 5xx), with an exponential backoff that follows the
+/// `Retry-After` header.
+///
+/// The throttle delay is counted from the last request of any client of the
+/// process, not only this one.
+pub struct HttpClient {
+    transport: Box<dyn Transport>,
+    base_url: String,
+    user_agent: String,
+    throttle: Duration,
+    retries: u32,
+    backoff: Duration,
+}
+
+impl HttpClient {
+    pub fn new(config: &Http) -> Self {
+        Self::with_transport(config, Box::new(UreqTransport))
+    }
+
+    pub fn with_transport(config: &Http, transport: Box<dyn Transport>) -> Self {
+        Self {
+            transport,
+            base_url: config.base_url.trim_end_matches('/').to_string(),
+            user_agent: config
+                .user_agent
+                .clone()
+                .unwrap_or_else(|| DEFAULT_USER_AGENT.to_string()),
+            throttle: Duration::from_millis(config.throttle_ms),
+            retries: config.retries,
+            backoff: Duration::from_millis(config.backoff_ms),
+        }
+    }
+
+    /// Sends a GET request for a path of the AoC website, with the given
+    /// session cookie.
+    pub fn get(&self, path: &str, session: &str) -> Result<Response, RuntimeError> {
+        self.send("GET", path, session, Vec::new())
+    }
+
+    /// Sends a POST request with form fields for a path of the AoC website,
+    /// with the given session cookie. Unlike GET requests, it is never
+    /// retried, as sending a wrong answer twice would be penalized twice.
+    pub fn post_form(
+        &self,
+        path: &str,
+        session: &str,
+        form: &[(&str, &str)],
+    ) -> Result<Response, RuntimeError> {
+        let form = form
+            .iter()
+            .map(|(name, value)| (name.to_string(), value.to_string()))
+            .collect();
+
+        self.send("POST", path, session, form)
+    }
+
+    fn send(
+        &self,
+        method: &'static str,
+        path: &str,
+        session: &str,
+        form: Vec<(String, String)>,
+    ) -> Result<Response, RuntimeError> {
+        let request = Request {
+            method,
+            url: format!("{}/{}", self.base_url, path.trim_start_matches('/')),
+            headers: vec![
+                ("Cookie", session.to_string()),
+                ("User-Agent", self.user_agent.clone()),
+            ],
+            form,
+        };
+
+        let retries = if method == "GET" { self.retries } else { 0 };
+        let mut attempt = 0;
+        loop {
+            self.wait_for_turn();
+            let result = self.transport.send(&request);
+            attempt += 1;
+
+            let retry_after = match &result {
+                Ok(response) if response.is_transient_failure() => response.retry_after,
+                Err(RuntimeError::NetworkError(_)) => None,
+                _ => return result,
+            };
+
+            if attempt > retries {
+                return result;
+            }
+
+            let delay = (self
+                .backoff
+                .saturating_mul(2u32.saturating_pow(attempt - 1)))
+            .max(retry_after.unwrap_or_default())
+            .min(MAX_RETRY_DELAY);
+            thread::sleep(delay);
+        }
+    }
+
+    /// Sleeps until the throttle delay has passed since the last request of
+    /// the process. The lock is held while sleeping, so that concurrent
+    /// requests take turns.
+    fn wait_for_turn(&self) {
+        let mut last_request = LAST_REQUEST
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+
+        if let Some(last_request) = *last_request {
+            thread::sleep(self.throttle.saturating_sub(last_request.elapsed()));
+        }
+
+        *last_request = Some(Instant::now());
+    }
+}
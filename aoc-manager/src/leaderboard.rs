@@ -0,0 +1,187 @@
+use std::collections::HashMap;
+
+use colored::Colorize;
+use serde::Deserialize;
+
+use crate::{calendar::Calendar, Challenge, Day, Year};
+
+/// A private leaderboard, as returned by the JSON API of the AoC website.
+#[derive(Debug, Clone, Deserialize)]
+pub struct Leaderboard {
+    pub owner_id: u64,
+    pub members: HashMap<String, Member>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Member {
+    pub id: u64,
+    pub name: Option<String>,
+    pub stars: u32,
+    pub local_score: u32,
+    /// The stars of the member, by day and then by part.
+    #[serde(default)]
+    pub completion_day_level: HashMap<String, HashMap<String, Star>>,
+}
+
+#[derive(Debug, Clone, Copy, Deserialize)]
+pub struct Star {
+    /// Unix timestamp of the moment the star was collected.
+    pub get_star_ts: i64,
+}
+
+impl Member {
+    pub fn display_name(&self) -> String {
+        self.name
+            .clone()
+            .unwrap_or_else(|| format!("(anonymous user #{})", self.id))
+    }
+
+    /// Returns the timestamp of the star of a part of a day, if collected.
+    pub fn star(&self, day: Day, part: u8) -> Option<i64> {
+        self.completion_day_level
+            .get(&day.0.to_string())
+            .and_then(|parts| parts.get(&part.to_string()))
+            .map(|star| star.get_star_ts)
+    }
+}
+
+impl Leaderboard {
+    /// Returns the members from first to last: by local score, then by number
+    /// of stars, then by the time of their last star.
+    pub fn standings(&self) -> Vec<&Member> {
+        let mut members = self.members.values().collect::<Vec<_>>();
+
+        members.sort_by_key(|member| {
+            let last_star = member
+                .completion_day_level
+                .values()
+                .flat_map(|parts| parts.values())
+                .map(|star| star.get_star_ts)
+                .max()
+                .unwrap_or(i64::MAX);
+
+            (
+                std::cmp::Reverse(member.local_score),
+                std::cmp::Reverse(member.stars),
+                last_star,
+            )
+        });
+
+        members
+    }
+
+    /// Prints the standings, with the stars of each member for every day of
+    /// the event.
+    pub fn render(&self, year: Year, calendar: &Calendar) {
+        let days = calendar.days(year).collect::<Vec<_>>();
+        let standings = self.standings();
+        let score_width = standings
+            .first()
+            .map_or(1, |member| member.local_score.to_string().len());
+        let margin = " ".repeat(score_width + 6);
+
+        let tens = days
+            .iter()
+            .map(|day| match day.0 / 10 {
+                0 => ' ',
+                n => char::from_digit(n, 10).unwrap_or(' '),
+            })
+            .collect::<String>();
+        let units = days
+            .iter()
+            .map(|day| char::from_digit(day.0 % 10, 10).unwrap_or(' '))
+            .collect::<String>();
+
+        println!("{margin}{}", tens.dimmed());
+        println!("{margin}{}", units.dimmed());
+
+        for (rank, member) in standings.iter().enumerate() {
+            let stars = days
+                .iter()
+                .map(|day| match (member.star(*day, 1), member.star(*day, 2)) {
+                    (Some(_), Some(_)) => "*".yellow(),
+                    (Some(_), None) => "*".white(),
+                    _ => ".".dimmed(),
+                })
+                .fold(String::new(), |line, star| format!("{line}{star}"));
+
+            println!(
+                "{:>3}) {:>score_width$} {stars} {}",
+                rank + 1,
+                member.local_score,
+                member.display_name()
+            );
+        }
+    }
+
+    /// Returns the members who collected a star on a day, with the times at
+    /// which they collected each part, in seconds from the moment the puzzle
+    /// unlocked. The members who finished the day come first, fastest first,
+    /// followed by those who only collected the first star.
+    pub fn day_times(
+        &self,
+        challenge: Challenge,
+        calendar: &Calendar,
+    ) -> Vec<(&Member, i64, Option<i64>)> {
+        let unlock = calendar.unlock_time(&challenge).timestamp();
+        let mut members = self
+            .members
+            .values()
+            .filter_map(|member| {
+                let part_1 = member.star(challenge.day, 1)? - unlock;
+
+                Some((
+                    member,
+                    part_1,
+                    member.star(challenge.day, 2).map(|part_2| part_2 - unlock),
+                ))
+            })
+            .collect::<Vec<_>>();
+
+        members.sort_by_key(|(_, part_1, part_2)| (part_2.unwrap_or(i64::MAX), *part_1));
+
+        members
+    }
+
+    /// Prints the times at which each member collected the stars of a day,
+    /// counted from the moment the puzzle unlocked, and the time between the
+    /// two parts.
+    pub fn render_day(&self, challenge: Challenge, calendar: &Calendar) {
+        let members = self.day_times(challenge, calendar);
+
+        println!(
+            "{}",
+            format!("     {:>13} {:>13} {:>13}", "part 1", "part 2", "delta").dimmed()
+        );
+
+        for (rank, (member, part_1, part_2)) in members.into_iter().enumerate() {
+            let delta = part_2.map(|part_2| format_duration(part_2 - part_1));
+
+            println!(
+                "{:>3}) {:>13} {:>13} {:>13} {}",
+                rank + 1,
+                format_duration(part_1),
+                part_2.map(format_duration).unwrap_or_default(),
+                delta.unwrap_or_default(),
+                member.display_name()
+            );
+        }
+    }
+}
+
+/// Formats a number of seconds as `[Nd ]HH:MM:SS`.
+pub fn format_duration(seconds: i64) -> String {
+    let (days, seconds) = (seconds / 86400, seconds % 86400);
+    let time = format!(
+        "{:02}:{:02}:{:02}",
+        seconds / 3600,
+        seconds % 3600 / 60,
+        seconds % 60
+    );
+
+    if days > 0 {
+        format!("{days}d {time}")
+    } else {
+        time
+    }
+}
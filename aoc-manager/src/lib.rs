@@ -0,0 +1,21 @@
+mod cache;
+pub mod calendar;
+mod challenge;
+pub mod commands;
+pub mod config;
+mod error;
+pub mod http;
+pub mod leaderboard;
+pub mod migrate;
+pub mod puzzle;
+pub mod runner;
+pub mod status;
+pub mod submit;
+pub mod template;
+pub mod validate;
+mod workspace;
+
+pub use cache::{Account, Answers, InputCache, RunRecord, LEADERBOARD_REFRESH};
+pub use challenge::{Challenge, Day, Year};
+pub use error::RuntimeError;
+pub use workspace::{DayCrate, Workspace};
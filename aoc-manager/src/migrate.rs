@@ -0,0 +1,112 @@
+/// Rewrites the `src/main.rs` of a day crate to the current layout, in which
+/// the input is loaded and the parts are run by `aoc_utils::main!`:
+///
+/// - the `const INPUT: &str = include_str!("input.txt");` of the oldest crates
+///   is removed;
+/// - the `main` function, which loads the input and calls the parts, is
+///   replaced with `aoc_utils::main!(part_1, part_2);`, keeping the parts that
+///   weren't commented out.
+///
+/// Each part must be run on the input, either `INPUT`, `aoc_utils::input!()`
+/// or a variable bound to one of them: a `main` function running a part on
+/// an example instead isn't migrated, as `aoc_utils::main!` would change what
+/// it computes.
+///
+/// The rest of the file, which holds the solution, is left untouched. Returns
+/// `None` if the file already has the current layout, and an explanation if it
+/// can't be migrated automatically.
+pub fn migrate_source(source: &str) -> Result<Option<String>, String> {
+    if source.lines().any(|line| line.contains("main!(")) {
+        return Ok(None);
+    }
+
+    let lines = source.lines().collect::<Vec<_>>();
+    let start = lines
+        .iter()
+        .position(|line| line.starts_with("fn main()"))
+        .ok_or("it has no `fn main()`")?;
+    let end = start
+        + lines[start..]
+            .iter()
+            .position(|line| *line == "}")
+            .ok_or("the end of its `main` function can't be found")?;
+
+    let statements = lines[start + 1..end]
+        .iter()
+        .map(|line| line.trim())
+        .filter(|line| !(line.is_empty() || line.starts_with("//")))
+        .collect::<Vec<_>>();
+
+    // The names bound to the input, such as `input` in
+    // `let input = &aoc_utils::input!();`
+    let mut bindings = Vec::new();
+    let mut parts = Vec::new();
+
+    for statement in &statements {
+        if let Some((name, value)) = statement
+            .strip_prefix("let ")
+            .and_then(|s| s.strip_suffix(';'))
+            .and_then(|s| s.split_once('='))
+        {
+            let name = name.trim();
+            if is_input(value.trim(), &bindings) {
+                bindings.push(name);
+            } else {
+                // Shadowed by something else
+                bindings.retain(|binding| *binding != name);
+            }
+            continue;
+        }
+
+        let Some((part, argument)) = ["part_1", "part_2"].into_iter().find_map(|part| {
+            statement
+                .strip_prefix(part)
+                .and_then(|s| s.strip_prefix('('))
+                .and_then(|s| s.strip_suffix(");"))
+                .map(|argument| (part, argument.trim()))
+        }) else {
+            return Err(format!(
+                "its `main` function does more than running the parts (`{statement}`)"
+            ));
+        };
+
+        if !is_input(argument, &bindings) {
+            return Err(format!(
+                "its `main` function runs `{part}` on `{argument}` rather than the input"
+            ));
+        }
+        if !parts.contains(&part) {
+            parts.push(part);
+        }
+    }
+
+    if parts.first() != Some(&"part_1") {
+        return Err("its `main` function doesn't run `part_1`".to_string());
+    }
+
+    let mut migrated = lines[..start]
+        .iter()
+        .filter(|line| {
+            !(line.starts_with("const ") && line.contains("include_str!(\"input.txt\")"))
+        })
+        .map(|line| line.to_string())
+        .collect::<Vec<_>>();
+    migrated.push(format!("aoc_utils::main!({});", parts.join(", ")));
+    migrated.extend(lines[end + 1..].iter().map(|line| line.to_string()));
+
+    let mut migrated = migrated.join("\n");
+    if source.ends_with('\n') {
+        migrated.push('\n');
+    }
+
+    Ok(Some(migrated))
+}
+
+/// Returns whether an expression is the input, possibly borrowed: the `INPUT`
+/// constant, the `input!` macro or one of the variables bound to them.
+fn is_input(expression: &str, bindings: &[&str]) -> bool {
+    let expression = expression.trim_start_matches('&');
+
+    matches!(expression, "INPUT" | "input!()" | "aoc_utils::input!()")
+        || bindings.contains(&expression)
+}
@@ -0,0 +1,60 @@
+/// The parts of the description of a puzzle that are used to fill templates.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Puzzle {
+    pub title: String,
+    pub example: String,
+}
+
+impl Puzzle {
+    /// Extracts the title and the first example of a puzzle from its page on
+    /// the AoC website. Missing parts are left empty.
+    pub fn from_html(html: &str) -> Self {
+        let title = between(html, "<h2>", "</h2>")
+            .map(|title| {
+                let title = unescape(&strip_tags(title));
+                let title = title.trim().trim_matches('-').trim();
+
+                // Remove the "Day N: " prefix
+                title
+                    .split_once(": ")
+                    .map(|(_, title)| title)
+                    .unwrap_or(title)
+                    .to_string()
+            })
+            .unwrap_or_default();
+
+        let example = between(html, "<pre><code>", "</code></pre>")
+            .map(|example| unescape(&strip_tags(example)))
+            .unwrap_or_default();
+
+        Self { title, example }
+    }
+}
+
+pub(crate) fn between<'a>(s: &'a str, start: &str, end: &str) -> Option<&'a str> {
+    let (_, rest) = s.split_once(start)?;
+    let (inner, _) = rest.split_once(end)?;
+
+    Some(inner)
+}
+
+pub(crate) fn strip_tags(s: &str) -> String {
+    s.split('<')
+        .enumerate()
+        .map(|(i, part)| {
+            if i == 0 {
+                part
+            } else {
+                part.split_once('>').map(|(_, text)| text).unwrap_or(part)
+            }
+        })
+        .collect()
+}
+
+fn unescape(s: &str) -> String {
+    s.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&amp;", "&")
+}
@@ -0,0 +1,229 @@
+use std::{
+    collections::HashSet,
+    io::{BufRead, BufReader},
+    path::{Path, PathBuf},
+    process::{Child, Stdio},
+    time::{Duration, Instant},
+};
+
+use notify::{RecommendedWatcher, RecursiveMode, Watcher};
+
+use crate::{
+    config::Config,
+    submit::{submit_answers, SubmitMode},
+    Account, Answers, Challenge, InputCache, RuntimeError,
+};
+
+/// Clears the screen before a run of the watch loop, if `watch.clear` is set.
+fn clear_screen(config: &Config) {
+    if config.watch.clear {
+        // Clear the screen and move the cursor to the top left corner
+        print!("\x1B[2J\x1B[1;1H");
+    }
+}
+
+fn run_command(challenge: Challenge, config: &Config) -> std::process::Command {
+    let mut command = std::process::Command::new("cargo");
+    command.current_dir(&config.root).arg("run");
+
+    if config.watch.release {
+        command.arg("--release");
+    }
+
+    command
+        .arg("--bin")
+        .arg(config.naming.crate_name(&challenge));
+    command
+}
+
+pub fn spawn_compiler(challenge: Challenge, config: &Config) -> Result<Child, RuntimeError> {
+    run_command(challenge, config).spawn().map_err(Into::into)
+}
+
+/// Runs the solution of a challenge until it exits, showing its output, and
+/// returns the answers it printed, or `None` if it failed.
+fn run_and_capture(challenge: Challenge, config: &Config) -> Result<Option<Answers>, RuntimeError> {
+    let mut child = run_command(challenge, config)
+        .stdout(Stdio::piped())
+        .spawn()?;
+    let mut output = String::new();
+
+    if let Some(stdout) = child.stdout.take() {
+        for line in BufReader::new(stdout).lines() {
+            let line = line?;
+            println!("{line}");
+            output.push_str(&line);
+            output.push('\n');
+        }
+    }
+
+    Ok(child
+        .wait()?
+        .success()
+        .then(|| Answers::from_output(&output)))
+}
+
+/// Runs the solution of a challenge every time its crate changes. Unless
+/// `submit` is [`SubmitMode::Never`], each run waits for the solution to exit,
+/// and its new answers are submitted.
+pub fn watch(
+    challenge: Challenge,
+    submit: SubmitMode,
+    config: &Config,
+) -> Result<(), RuntimeError> {
+    let path = config.root.join(config.naming.crate_name(&challenge));
+    let (tx, rx) = std::sync::mpsc::channel();
+
+    // Automatically select the best implementation for your platform.
+    // You can also access each implementation directly e.g. INotifyWatcher.
+    let mut watcher = RecommendedWatcher::new(tx, notify::Config::default())?;
+
+    // Add a path to be watched. All files and directories at that path and
+    // below will be monitored for changes.
+    watcher.watch(&path, RecursiveMode::Recursive)?;
+
+    if submit != SubmitMode::Never {
+        let mut declined = HashSet::new();
+        let run = |declined: &mut HashSet<_>| {
+            clear_screen(config);
+
+            match run_and_capture(challenge, config)? {
+                Some(answers) => submit_answers(challenge, &answers, submit, declined, config),
+                None => Ok(()),
+            }
+        };
+
+        if config.watch.run_on_start {
+            run(&mut declined)?;
+        }
+
+        while let Ok(res) = rx.recv() {
+            res?;
+            // Changes made during the run are covered by the next one
+            while rx.try_recv().is_ok() {}
+            run(&mut declined)?;
+        }
+
+        return Ok(());
+    }
+
+    let mut child: Option<Child> = None;
+    let spawn = || {
+        clear_screen(config);
+        spawn_compiler(challenge, config)
+    };
+
+    if config.watch.run_on_start {
+        child = Some(spawn()?);
+    }
+
+    for res in rx {
+        match res {
+            Ok(_) => match child.as_mut().map(|x| x.try_wait()) {
+                Some(Ok(None)) => {}
+                Some(Err(e)) => return Err(e.into()),
+                Some(Ok(Some(_))) | None => child = Some(spawn()?),
+            },
+            Err(error) => return Err(error.into()),
+        }
+    }
+
+    Ok(())
+}
+
+/// Builds the solution of a challenge, and returns the path of its executable.
+pub fn build_solution(challenge: Challenge, config: &Config) -> Result<PathBuf, RuntimeError> {
+    let output = std::process::Command::new("cargo")
+        .current_dir(&config.root)
+        .arg("build")
+        .arg("--quiet")
+        .arg("--message-format=json-render-diagnostics")
+        .arg("--bin")
+        .arg(config.naming.crate_name(&challenge))
+        .stderr(Stdio::inherit())
+        .output()?;
+
+    if !output.status.success() {
+        return Err(RuntimeError::SolutionFailed(challenge));
+    }
+
+    String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter_map(|line| serde_json::from_str::<serde_json::Value>(line).ok())
+        .find_map(|message| message["executable"].as_str().map(PathBuf::from))
+        .ok_or(RuntimeError::SolutionFailed(challenge))
+}
+
+/// Runs the solution of a challenge on the given input file, and returns the
+/// answers it printed along with the time it took, build excluded.
+pub fn run_solution(
+    challenge: Challenge,
+    input: &Path,
+    config: &Config,
+) -> Result<(Answers, Duration), RuntimeError> {
+    let executable = build_solution(challenge, config)?;
+
+    let start = Instant::now();
+    let output = std::process::Command::new(executable)
+        .env(aoc_utils::INPUT_VAR, input.canonicalize()?)
+        .stderr(Stdio::inherit())
+        .output()?;
+    let runtime = start.elapsed();
+
+    if output.status.success() {
+        Ok((
+            Answers::from_output(&String::from_utf8_lossy(&output.stdout)),
+            runtime,
+        ))
+    } else {
+        Err(RuntimeError::SolutionFailed(challenge))
+    }
+}
+
+/// Runs the tests of the crate of a challenge, and returns the number of tests
+/// that passed, or `None` if any of them failed.
+pub fn run_tests(challenge: Challenge, config: &Config) -> Result<Option<usize>, RuntimeError> {
+    let output = std::process::Command::new("cargo")
+        .current_dir(&config.root)
+        .arg("test")
+        .arg("--quiet")
+        .arg("-p")
+        .arg(config.naming.crate_name(&challenge))
+        .stderr(Stdio::null())
+        .output()?;
+
+    Ok(output
+        .status
+        .success()
+        .then(|| count_passed_tests(&String::from_utf8_lossy(&output.stdout))))
+}
+
+/// Returns the number of tests that passed according to the output of `cargo
+/// test`, which ends the tests of each target with a line such as
+/// `test result: ok. 2 passed; 0 failed; ...`.
+pub fn count_passed_tests(output: &str) -> usize {
+    output
+        .lines()
+        .filter_map(|line| {
+            let (passed, _) = line.strip_prefix("test result: ok. ")?.split_once(' ')?;
+
+            passed.parse::<usize>().ok()
+        })
+        .sum()
+}
+
+/// Runs the tests and the solution of a challenge, and records the results
+/// for `status`.
+pub fn measure(challenge: Challenge, config: &Config) -> Result<(), RuntimeError> {
+    let cache = InputCache::new(config);
+    let mut run = cache.get_run(&challenge)?;
+
+    // A crate without tests doesn't count as passing them
+    run.tests_passed = Some(run_tests(challenge, config)?.is_some_and(|passed| passed > 0));
+    if let Some(input) = cache.cached_input(&Account::Default, &challenge)? {
+        let (_, runtime) = run_solution(challenge, &input, config)?;
+        run.runtime_ms = Some(runtime.as_millis() as u64);
+    }
+
+    cache.set_run(&challenge, &run)
+}
@@ -0,0 +1,127 @@
+use std::collections::HashSet;
+
+use colored::{ColoredString, Colorize};
+use serde::Serialize;
+
+use crate::{config::Config, Account, Challenge, InputCache, RuntimeError, Year};
+
+/// The progress on a single day of an event, as shown by `status`.
+#[derive(Debug, Serialize)]
+pub struct DayStatus {
+    pub year: i32,
+    pub day: u32,
+    /// Whether the crate of the day is a member of the workspace.
+    pub crate_exists: bool,
+    /// Whether the input of the default account is cached.
+    pub input_cached: bool,
+    pub part_1: PartStatus,
+    pub part_2: PartStatus,
+    /// Whether the tests of the crate passed the last time they were run by
+    /// `status --run`, if they ever were.
+    pub tests_passed: Option<bool>,
+    /// Time taken by the solution the last time it was measured, in
+    /// milliseconds.
+    pub runtime_ms: Option<u64>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PartStatus {
+    /// No answer is recorded for the part.
+    Unsolved,
+    /// The answer of the default account is recorded.
+    Solved,
+    /// The free second part of the last day, which has no answer.
+    Free,
+}
+
+impl DayStatus {
+    pub fn new(
+        challenge: Challenge,
+        members: &HashSet<Challenge>,
+        config: &Config,
+    ) -> Result<Self, RuntimeError> {
+        let cache = InputCache::new(config);
+        let answers = cache.get_answers(&Account::Default, &challenge)?;
+        let run = cache.get_run(&challenge)?;
+        let solved = |answer: &Option<String>| {
+            if answer.is_some() {
+                PartStatus::Solved
+            } else {
+                PartStatus::Unsolved
+            }
+        };
+
+        Ok(Self {
+            year: challenge.year.0,
+            day: challenge.day.0,
+            crate_exists: members.contains(&challenge),
+            input_cached: cache.has_input(&Account::Default, &challenge),
+            part_1: solved(&answers.part_1),
+            part_2: if config.calendar.has_free_part_2(&challenge) {
+                PartStatus::Free
+            } else {
+                solved(&answers.part_2)
+            },
+            tests_passed: run.tests_passed,
+            runtime_ms: run.runtime_ms,
+        })
+    }
+}
+
+/// Prints the calendar of an event, with a line for each of its days.
+pub fn render(year: Year, days: &[DayStatus]) {
+    let stars = days
+        .iter()
+        .flat_map(|day| [day.part_1, day.part_2])
+        .filter(|part| *part == PartStatus::Solved)
+        .count();
+    let crates = days.iter().filter(|day| day.crate_exists).count();
+
+    println!(
+        "{} {}",
+        year.to_string().bold(),
+        format!("({crates}/{} days, {stars} ★)", days.len()).dimmed()
+    );
+    println!(
+        "{}",
+        format!(
+            " day {:^6} {:^6} {:^7} {:^7} {:^6} runtime",
+            "crate", "input", "part 1", "part 2", "tests"
+        )
+        .dimmed()
+    );
+
+    for day in days {
+        let runtime = day
+            .runtime_ms
+            .map(|ms| format!("{ms:>5} ms"))
+            .unwrap_or_default();
+
+        println!(
+            "  {:02} {} {} {} {} {} {runtime}",
+            day.day,
+            check(day.crate_exists.then_some(true), 6),
+            check(day.input_cached.then_some(true), 6),
+            star(day.part_1, 7),
+            star(day.part_2, 7),
+            check(day.tests_passed, 6),
+        );
+    }
+}
+
+fn check(value: Option<bool>, width: usize) -> ColoredString {
+    match value {
+        Some(true) => format!("{:^width$}", "✓").green(),
+        Some(false) => format!("{:^width$}", "✗").red(),
+        None => format!("{:^width$}", "·").dimmed(),
+    }
+}
+
+fn star(part: PartStatus, width: usize) -> ColoredString {
+    match part {
+        PartStatus::Solved => format!("{:^width$}", "★").yellow(),
+        PartStatus::Unsolved => format!("{:^width$}", "·").dimmed(),
+        PartStatus::Free => format!("{:^width$}", "free").dimmed(),
+    }
+}
@@ -0,0 +1,248 @@
+use std::collections::HashSet;
+
+use colored::Colorize;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    commands::confirm, config::Config, puzzle, runner::run_tests, Account, Answers, Challenge,
+    InputCache, RuntimeError,
+};
+
+/// Whether the watcher submits the answers of the solution.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SubmitMode {
+    #[default]
+    Never,
+    /// Ask before submitting each new answer.
+    Ask,
+    /// Submit every new answer without asking.
+    Always,
+}
+
+/// The verdict of the AoC website on a submitted answer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Verdict {
+    Correct,
+    /// A wrong answer, with a hint on its value when the website gives one.
+    Wrong(Option<Hint>),
+    /// An answer was submitted too recently. Holds the time left to wait, as
+    /// written by the website.
+    TooSoon(String),
+    /// The part was already solved, or its first part wasn't.
+    WrongLevel,
+    /// Any other message of the website, as text.
+    Unknown(String),
+}
+
+impl Verdict {
+    /// Reads the verdict from the page the website answers a submission with.
+    pub fn from_html(html: &str) -> Self {
+        let message = puzzle::between(html, "<article>", "</article>")
+            .map(puzzle::strip_tags)
+            .unwrap_or_default();
+
+        if message.contains("That's the right answer") {
+            Self::Correct
+        } else if message.contains("That's not the right answer") {
+            Self::Wrong(if message.contains("your answer is too high") {
+                Some(Hint::TooHigh)
+            } else if message.contains("your answer is too low") {
+                Some(Hint::TooLow)
+            } else {
+                None
+            })
+        } else if message.contains("You gave an answer too recently") {
+            Self::TooSoon(
+                puzzle::between(&message, "You have ", " left to wait")
+                    .unwrap_or("some time")
+                    .to_string(),
+            )
+        } else if message.contains("You don't seem to be solving the right level") {
+            Self::WrongLevel
+        } else {
+            Self::Unknown(message.trim().to_string())
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Hint {
+    TooHigh,
+    TooLow,
+}
+
+/// An answer that was rejected by the website.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct Guess {
+    pub answer: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hint: Option<Hint>,
+}
+
+/// The rejected answers of both parts of a challenge.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct Guesses {
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub part_1: Vec<Guess>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub part_2: Vec<Guess>,
+}
+
+impl Guesses {
+    pub fn part(&self, part: u8) -> &[Guess] {
+        if part == 1 {
+            &self.part_1
+        } else {
+            &self.part_2
+        }
+    }
+
+    pub fn record(&mut self, part: u8, guess: Guess) {
+        if part == 1 {
+            self.part_1.push(guess);
+        } else {
+            self.part_2.push(guess);
+        }
+    }
+
+    /// Returns why an answer is known to be wrong, from the answers already
+    /// rejected and their hints, if it is.
+    pub fn rules_out(&self, part: u8, answer: &str) -> Option<String> {
+        let value = answer.parse::<i128>().ok();
+
+        self.part(part).iter().find_map(|guess| {
+            if guess.answer == answer {
+                return Some("it was already rejected".to_string());
+            }
+
+            let guessed = guess.answer.parse::<i128>().ok()?;
+            match (guess.hint?, value?) {
+                (Hint::TooHigh, value) if value >= guessed => {
+                    Some(format!("{} was too high", guess.answer))
+                }
+                (Hint::TooLow, value) if value <= guessed => {
+                    Some(format!("{} was too low", guess.answer))
+                }
+                _ => None,
+            }
+        })
+    }
+}
+
+/// Submits the new answers printed by a run of the solution, for the watcher.
+///
+/// Only the first unsolved part is considered, and its answer is skipped if it
+/// is known to be wrong, if it was already declined during this watch, or if
+/// the tests of the crate fail or there are none. It is then submitted, after asking unless
+/// `mode` is [`SubmitMode::Always`]. The verdict is recorded: a correct answer
+/// as the answer of the part, after which the second part is considered, and
+/// a wrong one as a guess to avoid.
+pub fn submit_answers(
+    challenge: Challenge,
+    answers: &Answers,
+    mode: SubmitMode,
+    declined: &mut HashSet<(u8, String)>,
+    config: &Config,
+) -> Result<(), RuntimeError> {
+    let cache = InputCache::new(config);
+    let mut recorded = cache.get_answers(&Account::Default, &challenge)?;
+
+    for (part, answer) in [(1, &answers.part_1), (2, &answers.part_2)] {
+        let solved = if part == 1 {
+            &mut recorded.part_1
+        } else {
+            &mut recorded.part_2
+        };
+
+        if solved.is_some() {
+            continue;
+        }
+        if part == 2 && config.calendar.has_free_part_2(&challenge) {
+            return Ok(());
+        }
+        let Some(answer) = answer else {
+            return Ok(());
+        };
+        if declined.contains(&(part, answer.clone())) {
+            return Ok(());
+        }
+
+        let mut guesses = cache.get_guesses(&challenge)?;
+        if let Some(reason) = guesses.rules_out(part, answer) {
+            println!(
+                "{}",
+                format!("not submitting {answer} for part {part}, as {reason}").yellow()
+            );
+            return Ok(());
+        }
+
+        let reason = match run_tests(challenge, config)? {
+            Some(0) => Some("the crate has no tests"),
+            Some(_) => None,
+            None => Some("the tests fail"),
+        };
+        if let Some(reason) = reason {
+            println!(
+                "{}",
+                format!("not submitting {answer} for part {part}, as {reason}").yellow()
+            );
+            return Ok(());
+        }
+
+        if mode == SubmitMode::Ask
+            && !confirm(&format!(
+                "Submit {answer} for part {part} of day {challenge}?"
+            ))?
+        {
+            declined.insert((part, answer.clone()));
+            return Ok(());
+        }
+
+        match cache.submit(&challenge, part, answer)? {
+            Verdict::Correct => {
+                println!("{}", format!("{answer} is the right answer!").green());
+                *solved = Some(answer.clone());
+                cache.set_answers(&Account::Default, &challenge, &recorded)?;
+            }
+            Verdict::Wrong(hint) => {
+                let detail = match hint {
+                    Some(Hint::TooHigh) => " (too high)",
+                    Some(Hint::TooLow) => " (too low)",
+                    None => "",
+                };
+                println!("{}", format!("{answer} is wrong{detail}.").red());
+                guesses.record(
+                    part,
+                    Guess {
+                        answer: answer.clone(),
+                        hint,
+                    },
+                );
+                cache.set_guesses(&challenge, &guesses)?;
+                return Ok(());
+            }
+            Verdict::TooSoon(wait) => {
+                println!(
+                    "{}",
+                    format!("an answer was submitted too recently, wait {wait}.").yellow()
+                );
+                return Ok(());
+            }
+            Verdict::WrongLevel => {
+                println!(
+                    "{}",
+                    format!("part {part} is already solved, or can't be solved yet.").yellow()
+                );
+                return Ok(());
+            }
+            Verdict::Unknown(message) => {
+                println!("{}", format!("unexpected answer: {message}").yellow());
+                return Ok(());
+            }
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,146 @@
+use std::{
+    fs::ReadDir,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+use crate::{config::Config, puzzle::Puzzle, Challenge, RuntimeError};
+
+/// A template for the crate of a day, which is a directory inside the
+/// `paths.templates` directory of the configuration.
+#[derive(Debug)]
+pub struct Template {
+    dir: PathBuf,
+}
+
+impl Template {
+    pub fn find(name: &str, config: &Config) -> Result<Self, RuntimeError> {
+        let dir = config.template_dir(name);
+
+        if dir.join("Cargo.toml").is_file() {
+            Ok(Self { dir })
+        } else {
+            Err(RuntimeError::UnknownTemplate {
+                name: name.to_string(),
+                available: Self::available(config)?.join(", "),
+            })
+        }
+    }
+
+    /// Returns the names of the templates found in the templates directory.
+    pub fn available(config: &Config) -> Result<Vec<String>, RuntimeError> {
+        let mut names = std::fs::read_dir(&config.paths.templates)?
+            .filter_map(|entry| {
+                entry
+                    .map(|entry| {
+                        entry
+                            .path()
+                            .join("Cargo.toml")
+                            .is_file()
+                            .then(|| entry.file_name().to_string_lossy().to_string())
+                    })
+                    .transpose()
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        names.sort();
+        Ok(names)
+    }
+
+    /// Copies the template to `dst`, replacing the placeholders in every text
+    /// file.
+    pub fn instantiate<D>(&self, dst: D, placeholders: &Placeholders) -> Result<(), RuntimeError>
+    where
+        D: AsRef<Path>,
+    {
+        fn rec(
+            dir: ReadDir,
+            root: &Path,
+            dst: &Path,
+            placeholders: &Placeholders,
+        ) -> Result<(), RuntimeError> {
+            for entry in dir {
+                let entry = entry?;
+                let meta = entry.metadata()?;
+                let path = entry.path();
+                let relative_path = path
+                    .strip_prefix(root)
+                    .expect("we shouldn't end up with a different prefix here");
+
+                if meta.is_file() {
+                    match String::from_utf8(std::fs::read(&path)?) {
+                        Ok(text) => {
+                            std::fs::write(dst.join(relative_path), placeholders.substitute(&text))?
+                        }
+                        Err(_) => {
+                            std::fs::copy(&path, dst.join(relative_path))?;
+                        }
+                    }
+                } else if meta.is_dir() {
+                    std::fs::create_dir(dst.join(relative_path))?;
+                    rec(std::fs::read_dir(path)?, root, dst, placeholders)?;
+                }
+            }
+
+            Ok(())
+        }
+
+        std::fs::create_dir(&dst)?;
+        rec(
+            std::fs::read_dir(&self.dir)?,
+            &self.dir,
+            dst.as_ref(),
+            placeholders,
+        )
+    }
+}
+
+/// The values of the placeholders of a template, which are written
+/// `{{crate_name}}`, `{{year}}`, `{{day}}`, `{{title}}` and `{{example}}` in the
+/// template's files.
+#[derive(Debug)]
+pub struct Placeholders {
+    pub crate_name: String,
+    pub challenge: Challenge,
+    pub puzzle: Puzzle,
+}
+
+impl Placeholders {
+    pub fn substitute(&self, text: &str) -> String {
+        text.replace("{{crate_name}}", &self.crate_name)
+            .replace("{{year}}", &self.challenge.year.to_string())
+            .replace("{{day}}", &self.challenge.day.to_string())
+            .replace("{{title}}", &self.puzzle.title)
+            .replace("{{example}}", &self.puzzle.example)
+    }
+}
+
+/// A dependency to add to a new crate, written `name` or `name@version` on the
+/// command line. Without a version, any version is accepted.
+#[derive(Debug, Clone)]
+pub struct Dependency {
+    pub name: String,
+    pub version: String,
+}
+
+impl FromStr for Dependency {
+    type Err = RuntimeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (name, version) = s.split_once('@').unwrap_or((s, "*"));
+
+        if name.is_empty()
+            || version.is_empty()
+            || !name
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+        {
+            Err(RuntimeError::InvalidDependency(s.to_string()))
+        } else {
+            Ok(Self {
+                name: name.to_string(),
+                version: version.to_string(),
+            })
+        }
+    }
+}
@@ -0,0 +1,62 @@
+use crate::puzzle::between;
+
+/// Sent instead of an input when the session is missing or expired.
+const LOGGED_OUT_MESSAGE: &str = "Please log in";
+
+/// Sent instead of an input when the puzzle isn't unlocked yet.
+const LOCKED_MESSAGE: &str = "before it unlocks";
+
+/// Plain error bodies, which are only recognized as a whole, since an input
+/// may well start with the same numbers.
+const ERROR_MESSAGES: [&str; 3] = [
+    "400 Bad Request",
+    "404 Not Found",
+    "500 Internal Server Error",
+];
+
+/// Whether the AoC website answered as if the session was missing or invalid.
+pub fn is_logged_out_message(body: &str) -> bool {
+    body.contains(LOGGED_OUT_MESSAGE)
+}
+
+pub fn is_locked_message(body: &str) -> bool {
+    body.contains(LOCKED_MESSAGE)
+}
+
+fn is_html(body: &str) -> bool {
+    let start = body.trim_start().to_ascii_lowercase();
+
+    start.starts_with("<!doctype html") || start.starts_with("<html")
+}
+
+/// Checks that the body of a response, or a cached file, looks like a puzzle
+/// input. Returns why it doesn't otherwise.
+pub fn check_input(body: &str) -> Result<(), String> {
+    if body.trim().is_empty() {
+        Err("it is empty".to_string())
+    } else if is_html(body) {
+        Err("it is an HTML page".to_string())
+    } else if is_logged_out_message(body) {
+        Err("it is the message AoC sends when the session is invalid".to_string())
+    } else if is_locked_message(body) {
+        Err("it is the message AoC sends before the puzzle unlocks".to_string())
+    } else if ERROR_MESSAGES.contains(&body.trim()) {
+        Err(format!("it is an error message (\"{}\")", body.trim()))
+    } else {
+        Ok(())
+    }
+}
+
+/// Returns the name of the user that a page of the AoC website was served to,
+/// or `None` if it was served to a logged out user.
+pub fn logged_in_user(html: &str) -> Option<String> {
+    if !html.contains("[Log Out]") {
+        return None;
+    }
+
+    Some(
+        between(html, "<div class=\"user\">", "<")
+            .map(|name| name.trim().to_string())
+            .unwrap_or_default(),
+    )
+}
@@ -0,0 +1,224 @@
+use std::path::{Path, PathBuf};
+
+use toml_edit::{value, Array, DocumentMut, Item};
+
+use crate::{config::Config, template::Dependency, Challenge, RuntimeError};
+
+/// The root `Cargo.toml` of the workspace, edited in a way that preserves its
+/// formatting.
+#[derive(Debug)]
+pub struct Workspace {
+    document: DocumentMut,
+    path: PathBuf,
+}
+
+impl Workspace {
+    /// Opens the manifest of the workspace whose root directory is `root`.
+    pub fn open(root: &Path) -> Result<Self, RuntimeError> {
+        let path = root.join("Cargo.toml");
+        let document = std::fs::read_to_string(&path)?.parse::<DocumentMut>()?;
+
+        if document
+            .get("workspace")
+            .and_then(Item::as_table)
+            .and_then(|workspace| workspace.get("members"))
+            .is_some_and(Item::is_array)
+        {
+            Ok(Self { document, path })
+        } else {
+            Err(RuntimeError::MissingWorkspace)
+        }
+    }
+
+    fn members_mut(&mut self) -> &mut Array {
+        self.document["workspace"]["members"]
+            .as_array_mut()
+            .expect("a constructed Workspace struct should always have a `members` array")
+    }
+
+    pub fn members(&self) -> &Array {
+        self.document["workspace"]["members"]
+            .as_array()
+            .expect("a constructed Workspace struct should always have a `members` array")
+    }
+
+    /// Sorts the members and puts each of them on its own line. Comments in
+    /// front of a member are kept with it.
+    fn format_members(&mut self) {
+        let members = self.members_mut();
+
+        members.sort_by(|a, b| a.as_str().cmp(&b.as_str()));
+
+        for member in members.iter_mut() {
+            let decor = member.decor_mut();
+            let has_comment = decor
+                .prefix()
+                .and_then(|prefix| prefix.as_str())
+                .is_some_and(|prefix| prefix.contains('#'));
+
+            if !has_comment {
+                decor.set_prefix("\n    ");
+            }
+            decor.set_suffix("");
+        }
+
+        members.set_trailing_comma(true);
+        members.set_trailing("\n");
+    }
+
+    pub fn position(&self, crate_name: &str) -> Option<usize> {
+        self.members()
+            .iter()
+            .position(|member| member.as_str() == Some(crate_name))
+    }
+
+    pub fn get_days(&self, config: &Config) -> Vec<Challenge> {
+        self.members()
+            .iter()
+            .filter_map(|v| {
+                v.as_str()
+                    .and_then(|s| config.naming.parse_crate_name(s).ok())
+            })
+            .collect()
+    }
+
+    pub fn add_day(&mut self, challenge: Challenge, config: &Config) -> Result<(), RuntimeError> {
+        let crate_name = config.naming.crate_name(&challenge);
+
+        if self.position(&crate_name).is_some() {
+            Err(RuntimeError::DayAlreadyPresent(challenge))
+        } else {
+            self.members_mut().push(crate_name);
+            self.format_members();
+            Ok(())
+        }
+    }
+
+    pub fn remove(&mut self, crate_name: &str) -> Result<(), RuntimeError> {
+        let index = self
+            .position(crate_name)
+            .ok_or_else(|| RuntimeError::MemberNotPresent(crate_name.to_string()))?;
+
+        self.members_mut().remove(index);
+        self.format_members();
+
+        Ok(())
+    }
+
+    pub fn rename(&mut self, from: &str, to: &str) -> Result<(), RuntimeError> {
+        let index = self
+            .position(from)
+            .ok_or_else(|| RuntimeError::MemberNotPresent(from.to_string()))?;
+
+        if self.position(to).is_some() {
+            return Err(RuntimeError::MemberAlreadyPresent(to.to_string()));
+        }
+
+        // Keep the comments of the member
+        let decor = self
+            .members()
+            .get(index)
+            .map(|member| member.decor().clone())
+            .unwrap_or_default();
+        let mut value = toml_edit::Value::from(to);
+        *value.decor_mut() = decor;
+
+        self.members_mut().replace_formatted(index, value);
+        self.format_members();
+
+        Ok(())
+    }
+
+    pub fn write(self) -> Result<(), RuntimeError> {
+        std::fs::write(&self.path, self.document.to_string())?;
+        Ok(())
+    }
+}
+
+#[derive(Debug)]
+pub struct DayCrate {
+    document: DocumentMut,
+    crate_name: String,
+    dir: PathBuf,
+}
+
+impl DayCrate {
+    pub fn new(challenge: Challenge, config: &Config) -> Result<Self, RuntimeError> {
+        let crate_name = config.naming.crate_name(&challenge);
+
+        Self::open(config.root.join(&crate_name), crate_name)
+    }
+
+    /// Opens the crate named `crate_name` in `dir`, which may differ from the
+    /// final location of the crate while it is being created.
+    pub fn open(dir: PathBuf, crate_name: String) -> Result<Self, RuntimeError> {
+        let document = std::fs::read_to_string(dir.join("Cargo.toml"))?.parse::<DocumentMut>()?;
+
+        if document.get("package").is_some_and(Item::is_table_like) {
+            Ok(Self {
+                document,
+                crate_name,
+                dir,
+            })
+        } else {
+            Err(RuntimeError::MissingPackage(crate_name))
+        }
+    }
+
+    pub fn set_name(&mut self) {
+        self.document["package"]["name"] = value(self.crate_name.clone());
+    }
+
+    pub fn add_dependency(&mut self, dependency: &Dependency) {
+        self.document["dependencies"][&dependency.name] = value(dependency.version.clone());
+    }
+
+    /// Whether the crate was created offline, and its input still has to be
+    /// fetched.
+    pub fn is_pending_fetch(&self) -> bool {
+        self.document["package"]
+            .get("metadata")
+            .and_then(|metadata| metadata.get("aoc"))
+            .and_then(|aoc| aoc.get("pending-fetch"))
+            .and_then(Item::as_bool)
+            .unwrap_or(false)
+    }
+
+    pub fn set_pending_fetch(&mut self, pending: bool) {
+        let metadata = &mut self.document["package"]["metadata"];
+
+        if pending {
+            if metadata.is_none() {
+                let mut table = toml_edit::Table::new();
+                table.set_implicit(true);
+                *metadata = Item::Table(table);
+            }
+
+            if let Some(metadata) = metadata.as_table_like_mut() {
+                metadata.entry("aoc").or_insert_with(toml_edit::table)["pending-fetch"] =
+                    value(true);
+            }
+        } else if let Some(metadata) = metadata.as_table_like_mut() {
+            if let Some(aoc) = metadata.get_mut("aoc").and_then(Item::as_table_like_mut) {
+                aoc.remove("pending-fetch");
+
+                if aoc.is_empty() {
+                    metadata.remove("aoc");
+                }
+            }
+
+            if metadata.is_empty() {
+                self.document["package"]
+                    .as_table_like_mut()
+                    .expect("a constructed DayCrate struct should always have a [package] table")
+                    .remove("metadata");
+            }
+        }
+    }
+
+    pub fn write(self) -> Result<(), RuntimeError> {
+        std::fs::write(self.dir.join("Cargo.toml"), self.document.to_string())?;
+
+        Ok(())
+    }
+}
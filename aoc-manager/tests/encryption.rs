@@ -0,0 +1,81 @@
+use aoc_manager::{config::Config, Account, Answers, Challenge, Day, InputCache, Year};
+use aoc_utils::{encrypted_path, CacheKey, DecryptionError};
+
+fn challenge() -> Challenge {
+    Challenge::new(Day::new(3).unwrap(), Year::new(2023).unwrap())
+}
+
+/// A configuration rooted in a temporary directory, with a key for the
+/// encrypted cache.
+fn config() -> (tempfile::TempDir, Config) {
+    let dir = tempfile::tempdir().unwrap();
+    let config = Config::default().with_root(dir.path());
+    std::fs::write(&config.paths.cache_key, "correct horse battery staple\n").unwrap();
+    std::fs::create_dir_all(&config.paths.cache).unwrap();
+
+    (dir, config)
+}
+
+#[test]
+fn key_round_trip() {
+    let key = CacheKey::from_passphrase("secret");
+    let encrypted = key.encrypt(b"1 2 3\n");
+
+    assert_ne!(encrypted, b"1 2 3\n");
+    assert_eq!(key.encrypt(b"1 2 3\n"), encrypted);
+    assert_eq!(key.decrypt(&encrypted).unwrap(), b"1 2 3\n");
+    assert!(matches!(
+        CacheKey::from_passphrase("other").decrypt(&encrypted),
+        Err(DecryptionError::WrongKey)
+    ));
+    assert!(matches!(
+        key.decrypt(b"1 2 3\n"),
+        Err(DecryptionError::InvalidFormat)
+    ));
+}
+
+#[test]
+fn encrypt_and_decrypt_cache() {
+    let (_dir, config) = config();
+    let cache = InputCache::new(&config);
+    let input = cache.input_path(&Account::Default, &challenge());
+    std::fs::write(&input, "1 2 3\n").unwrap();
+
+    assert_eq!(cache.encrypt_all().unwrap(), 1);
+    assert_eq!(cache.encrypt_all().unwrap(), 0);
+
+    std::fs::remove_file(&input).unwrap();
+    assert!(cache.has_input(&Account::Default, &challenge()));
+    assert_eq!(cache.get_present(&Account::Default).unwrap(), [challenge()]);
+    assert_eq!(
+        cache.read_cached(&input).unwrap().as_deref(),
+        Some("1 2 3\n")
+    );
+
+    assert_eq!(cache.decrypt_all(false).unwrap(), 1);
+    assert_eq!(std::fs::read_to_string(&input).unwrap(), "1 2 3\n");
+    assert_eq!(cache.decrypt_all(false).unwrap(), 0);
+}
+
+#[test]
+fn encrypt_on_write() {
+    let (_dir, mut config) = config();
+    config.cache.encrypt = true;
+    let cache = InputCache::new(&config);
+    let answers = Answers {
+        part_1: Some("42".to_string()),
+        part_2: None,
+    };
+
+    cache
+        .set_answers(&Account::Default, &challenge(), &answers)
+        .unwrap();
+    let path = cache.answers_path(&Account::Default, &challenge());
+    std::fs::remove_file(&path).unwrap();
+
+    assert!(encrypted_path(&path).is_file());
+    assert_eq!(
+        cache.get_answers(&Account::Default, &challenge()).unwrap(),
+        answers
+    );
+}
@@ -0,0 +1,416 @@
+use std::{cell::RefCell, collections::VecDeque, rc::Rc, time::Duration};
+
+use aoc_manager::{
+    config::{Config, Http},
+    http::{parse_retry_after, HttpClient, Request, Response, Transport},
+    leaderboard::format_duration,
+    submit::{Hint, Verdict},
+    Account, Challenge, Day, InputCache, RuntimeError, Year, LEADERBOARD_REFRESH,
+};
+
+/// Answers requests with queued responses, and records them.
+#[derive(Clone, Default)]
+struct FakeTransport {
+    responses: Rc<RefCell<VecDeque<Response>>>,
+    requests: Rc<RefCell<Vec<Request>>>,
+}
+
+impl FakeTransport {
+    fn with_responses(responses: impl IntoIterator<Item = (u16, &'static str)>) -> Self {
+        let transport = Self::default();
+        transport
+            .responses
+            .borrow_mut()
+            .extend(responses.into_iter().map(|(status, body)| Response {
+                status,
+                body: body.to_string(),
+                retry_after: None,
+            }));
+
+        transport
+    }
+}
+
+impl Transport for FakeTransport {
+    fn send(&self, request: &Request) -> Result<Response, RuntimeError> {
+        self.requests.borrow_mut().push(request.clone());
+
+        Ok(self
+            .responses
+            .borrow_mut()
+            .pop_front()
+            .expect("the fake transport should have a response left"))
+    }
+}
+
+fn http_config() -> Http {
+    Http {
+        base_url: "https://aoc.example.com/".to_string(),
+        user_agent: Some("tests".to_string()),
+        throttle_ms: 0,
+        retries: 2,
+        backoff_ms: 0,
+    }
+}
+
+fn challenge() -> Challenge {
+    Challenge::new(Day::new(3).unwrap(), Year::new(2023).unwrap())
+}
+
+/// A configuration rooted in a temporary directory, with a session for the
+/// default account.
+fn config() -> (tempfile::TempDir, Config) {
+    let dir = tempfile::tempdir().unwrap();
+    let mut config = Config::default().with_root(dir.path());
+    config.http = http_config();
+    std::fs::write(&config.paths.session, "session=abc\n").unwrap();
+
+    (dir, config)
+}
+
+#[test]
+fn client_sends_headers() {
+    let transport = FakeTransport::with_responses([(200, "ok")]);
+    let client = HttpClient::with_transport(&http_config(), Box::new(transport.clone()));
+
+    let response = client.get("/2023/day/3/input", "session=abc").unwrap();
+
+    assert_eq!(response.body, "ok");
+    let requests = transport.requests.borrow();
+    assert_eq!(requests[0].url, "https://aoc.example.com/2023/day/3/input");
+    assert!(requests[0]
+        .headers
+        .contains(&("User-Agent", "tests".to_string())));
+    assert!(requests[0]
+        .headers
+        .contains(&("Cookie", "session=abc".to_string())));
+}
+
+#[test]
+fn client_retries_server_errors() {
+    let transport = FakeTransport::with_responses([(503, ""), (429, ""), (200, "ok")]);
+    let client = HttpClient::with_transport(&http_config(), Box::new(transport.clone()));
+
+    assert_eq!(client.get("input", "").unwrap().body, "ok");
+    assert_eq!(transport.requests.borrow().len(), 3);
+}
+
+#[test]
+fn client_gives_up_after_retries() {
+    let transport = FakeTransport::with_responses([(500, ""), (500, ""), (500, "")]);
+    let client = HttpClient::with_transport(&http_config(), Box::new(transport.clone()));
+
+    assert_eq!(client.get("input", "").unwrap().status, 500);
+    assert_eq!(transport.requests.borrow().len(), 3);
+}
+
+#[test]
+fn client_does_not_retry_client_errors() {
+    let transport = FakeTransport::with_responses([(404, "")]);
+    let client = HttpClient::with_transport(&http_config(), Box::new(transport.clone()));
+
+    assert_eq!(client.get("input", "").unwrap().status, 404);
+    assert_eq!(transport.requests.borrow().len(), 1);
+}
+
+#[test]
+fn retry_after_accepts_seconds_and_dates() {
+    let now = chrono::DateTime::parse_from_rfc3339("2015-10-21T07:28:00Z")
+        .unwrap()
+        .to_utc();
+
+    assert_eq!(
+        parse_retry_after(" 120 ", now),
+        Some(Duration::from_secs(120))
+    );
+    assert_eq!(
+        parse_retry_after("Wed, 21 Oct 2015 07:29:30 GMT", now),
+        Some(Duration::from_secs(90))
+    );
+    assert_eq!(
+        parse_retry_after("Wed, 21 Oct 2015 07:00:00 GMT", now),
+        Some(Duration::ZERO)
+    );
+    assert_eq!(parse_retry_after("soon", now), None);
+}
+
+#[test]
+fn clients_share_the_throttle() {
+    let config = Http {
+        throttle_ms: 100,
+        ..http_config()
+    };
+    let transport = FakeTransport::with_responses([(200, "ok"), (200, "ok")]);
+    let first = HttpClient::with_transport(&config, Box::new(transport.clone()));
+    let second = HttpClient::with_transport(&config, Box::new(transport.clone()));
+
+    first.get("input", "").unwrap();
+    let start = std::time::Instant::now();
+    second.get("input", "").unwrap();
+
+    assert!(start.elapsed() >= Duration::from_millis(90));
+}
+
+#[test]
+fn fetch_caches_the_input() {
+    let (_dir, config) = config();
+    let transport = FakeTransport::with_responses([(200, "1 2 3\n")]);
+    let client = HttpClient::with_transport(&config.http, Box::new(transport.clone()));
+    let cache = InputCache::with_client(&config, client);
+
+    assert_eq!(
+        cache.fetch(&Account::Default, &challenge()).unwrap(),
+        "1 2 3\n"
+    );
+    // The second fetch reads the cache, without any request
+    assert_eq!(
+        cache.fetch(&Account::Default, &challenge()).unwrap(),
+        "1 2 3\n"
+    );
+    assert_eq!(transport.requests.borrow().len(), 1);
+    assert_eq!(cache.get_present(&Account::Default).unwrap(), [challenge()]);
+}
+
+#[test]
+fn fetch_rejects_invalid_inputs() {
+    let (_dir, config) = config();
+    let transport = FakeTransport::with_responses([
+        (
+            400,
+            "Puzzle inputs differ by user.  Please log in to get your puzzle input.",
+        ),
+        (
+            404,
+            "Please don't repeatedly request this endpoint before it unlocks!",
+        ),
+        (200, "<!DOCTYPE html>\n<html></html>"),
+    ]);
+    let client = HttpClient::with_transport(&config.http, Box::new(transport));
+    let cache = InputCache::with_client(&config, client);
+
+    assert!(matches!(
+        cache.fetch(&Account::Default, &challenge()),
+        Err(RuntimeError::SessionInvalid(Account::Default))
+    ));
+    assert!(matches!(
+        cache.fetch(&Account::Default, &challenge()),
+        Err(RuntimeError::PuzzleLocked(_))
+    ));
+    assert!(matches!(
+        cache.fetch(&Account::Default, &challenge()),
+        Err(RuntimeError::InvalidInput { .. })
+    ));
+    assert!(!cache.input_path(&Account::Default, &challenge()).exists());
+}
+
+#[test]
+fn sessions_reject_unsafe_account_names() {
+    let (_dir, config) = config();
+    let cache = InputCache::new(&config);
+
+    std::fs::write(
+        &config.paths.sessions,
+        "[sessions]\nalice = \"session=a\"\nbob-2_b = \"session=b\"\n",
+    )
+    .unwrap();
+    assert_eq!(
+        cache.get_accounts().unwrap(),
+        [
+            Account::Default,
+            Account::Named("alice".to_string()),
+            Account::Named("bob-2_b".to_string())
+        ]
+    );
+
+    for name in ["\"../x\"", "\"a/b\"", "\"\"", "leaderboards", "default"] {
+        std::fs::write(
+            &config.paths.sessions,
+            format!("[sessions]\n{name} = \"session=x\"\n"),
+        )
+        .unwrap();
+        assert!(
+            matches!(
+                cache.get_accounts(),
+                Err(RuntimeError::InvalidAccountName(_))
+            ),
+            "{name} should be rejected"
+        );
+    }
+}
+
+#[test]
+fn check_session_reports_server_errors() {
+    let (_dir, config) = config();
+    let transport = FakeTransport::with_responses([
+        (
+            200,
+            r#"<div class="user">someone</div><a href="/auth/logout">[Log Out]</a>"#,
+        ),
+        (200, "<a href=\"/auth/login\">[Log In]</a>"),
+        (503, "Service Unavailable"),
+        (503, "Service Unavailable"),
+        (503, "Service Unavailable"),
+    ]);
+    let client = HttpClient::with_transport(&config.http, Box::new(transport));
+    let cache = InputCache::with_client(&config, client);
+
+    assert_eq!(
+        cache.check_session(&Account::Default).unwrap().as_deref(),
+        Some("someone")
+    );
+    assert_eq!(cache.check_session(&Account::Default).unwrap(), None);
+    // An outage isn't mistaken for an invalid session
+    assert!(matches!(
+        cache.check_session(&Account::Default),
+        Err(RuntimeError::HttpStatus { status: 503, .. })
+    ));
+}
+
+#[test]
+fn submit_posts_the_answer() {
+    let (_dir, config) = config();
+    let transport = FakeTransport::with_responses([
+        (
+            200,
+            "<article><p>That's not the right answer; your answer is too low.</p></article>",
+        ),
+        (500, ""),
+    ]);
+    let client = HttpClient::with_transport(&config.http, Box::new(transport.clone()));
+    let cache = InputCache::with_client(&config, client);
+
+    assert_eq!(
+        cache.submit(&challenge(), 2, "1234").unwrap(),
+        Verdict::Wrong(Some(Hint::TooLow))
+    );
+    // Submissions are never retried
+    assert!(matches!(
+        cache.submit(&challenge(), 2, "1235"),
+        Err(RuntimeError::HttpStatus { status: 500, .. })
+    ));
+
+    let requests = transport.requests.borrow();
+    assert_eq!(requests.len(), 2);
+    assert_eq!(requests[0].method, "POST");
+    assert_eq!(requests[0].url, "https://aoc.example.com/2023/day/3/answer");
+    assert_eq!(
+        requests[0].form,
+        [
+            ("level".to_string(), "2".to_string()),
+            ("answer".to_string(), "1234".to_string())
+        ]
+    );
+}
+
+/// A private leaderboard of 2023, in which day 3 unlocked at 1701579600.
+const LEADERBOARD: &str = r#"{
+    "owner_id": 1,
+    "event": "2023",
+    "members": {
+        "1": {
+            "id": 1, "name": "Alice", "stars": 2, "local_score": 10,
+            "completion_day_level": {
+                "3": {"1": {"get_star_ts": 1701580200}, "2": {"get_star_ts": 1701580800}}
+            }
+        },
+        "2": {
+            "id": 2, "name": null, "stars": 2, "local_score": 10,
+            "completion_day_level": {
+                "3": {"1": {"get_star_ts": 1701579900}, "2": {"get_star_ts": 1701581600}}
+            }
+        },
+        "3": {
+            "id": 3, "name": "Carol", "stars": 1, "local_score": 4,
+            "completion_day_level": {"3": {"1": {"get_star_ts": 1701579700}}}
+        },
+        "4": {
+            "id": 4, "name": "Dave", "stars": 2, "local_score": 12,
+            "completion_day_level": {
+                "1": {"1": {"get_star_ts": 1701406900}, "2": {"get_star_ts": 1701407000}}
+            }
+        }
+    }
+}"#;
+
+#[test]
+fn leaderboard_is_parsed_and_ranked() {
+    let (_dir, config) = config();
+    let transport = FakeTransport::with_responses([(200, LEADERBOARD)]);
+    let client = HttpClient::with_transport(&config.http, Box::new(transport.clone()));
+    let cache = InputCache::with_client(&config, client);
+    let year = Year::new(2023).unwrap();
+
+    let (leaderboard, age) = cache.fetch_leaderboard(year, 1).unwrap();
+
+    assert!(age.is_zero());
+    assert_eq!(
+        transport.requests.borrow()[0].url,
+        "https://aoc.example.com/2023/leaderboard/private/view/1.json"
+    );
+    // Ties on the score and the stars are broken by the earliest last star
+    assert_eq!(
+        leaderboard
+            .standings()
+            .iter()
+            .map(|member| member.display_name())
+            .collect::<Vec<_>>(),
+        ["Dave", "Alice", "(anonymous user #2)", "Carol"]
+    );
+    assert_eq!(
+        leaderboard
+            .day_times(challenge(), &config.calendar)
+            .iter()
+            .map(|(member, part_1, part_2)| (member.id, *part_1, *part_2))
+            .collect::<Vec<_>>(),
+        [(1, 600, Some(1200)), (2, 300, Some(2000)), (3, 100, None)]
+    );
+}
+
+#[test]
+fn leaderboard_is_cached_until_refresh() {
+    let (_dir, config) = config();
+    let transport = FakeTransport::with_responses([(200, LEADERBOARD), (200, LEADERBOARD)]);
+    let client = HttpClient::with_transport(&config.http, Box::new(transport.clone()));
+    let cache = InputCache::with_client(&config, client);
+    let year = Year::new(2023).unwrap();
+
+    cache.fetch_leaderboard(year, 1).unwrap();
+    let (leaderboard, _) = cache.fetch_leaderboard(year, 1).unwrap();
+    assert_eq!(leaderboard.owner_id, 1);
+    assert_eq!(transport.requests.borrow().len(), 1);
+
+    // Once the cached copy is older than the refresh delay, it is fetched again
+    let modified = std::time::SystemTime::now() - LEADERBOARD_REFRESH;
+    std::fs::File::options()
+        .write(true)
+        .open(cache.leaderboard_path(year, 1))
+        .unwrap()
+        .set_modified(modified)
+        .unwrap();
+
+    let (_, age) = cache.fetch_leaderboard(year, 1).unwrap();
+    assert!(age.is_zero());
+    assert_eq!(transport.requests.borrow().len(), 2);
+}
+
+#[test]
+fn leaderboard_without_access_is_unavailable() {
+    let (_dir, config) = config();
+    let transport = FakeTransport::with_responses([(200, "<!DOCTYPE html><html></html>")]);
+    let client = HttpClient::with_transport(&config.http, Box::new(transport.clone()));
+    let cache = InputCache::with_client(&config, client);
+    let year = Year::new(2023).unwrap();
+
+    assert!(matches!(
+        cache.fetch_leaderboard(year, 7),
+        Err(RuntimeError::LeaderboardUnavailable(7))
+    ));
+    assert!(!cache.leaderboard_path(year, 7).exists());
+}
+
+#[test]
+fn durations_are_formatted() {
+    assert_eq!(format_duration(0), "00:00:00");
+    assert_eq!(format_duration(3661), "01:01:01");
+    assert_eq!(format_duration(2 * 86400 + 59), "2d 00:00:59");
+}
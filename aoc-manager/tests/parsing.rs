@@ -0,0 +1,224 @@
+use aoc_manager::{
+    calendar::Calendar,
+    migrate::migrate_source,
+    runner,
+    submit::{Guess, Guesses, Hint, Verdict},
+    validate, Answers, Challenge, Day, RuntimeError, Year,
+};
+
+fn challenge(year: i32, day: u32) -> Challenge {
+    Challenge::new(Day::new(day).unwrap(), Year::new(year).unwrap())
+}
+
+#[test]
+fn days_and_years_are_checked() {
+    assert!(Day::new(0).is_err());
+    assert!(Day::new(26).is_err());
+    assert_eq!("7".parse::<Day>().unwrap().get(), 7);
+    assert!(Year::new(2014).is_err());
+    assert_eq!("2015".parse::<Year>().unwrap().get(), 2015);
+    assert_eq!(challenge(2023, 7).input_name(), "2023_07.txt");
+    assert_eq!(
+        Challenge::from_input_name("2023_07.txt").unwrap(),
+        challenge(2023, 7)
+    );
+}
+
+#[test]
+fn calendar_days() {
+    let calendar = Calendar::default();
+
+    assert_eq!(calendar.days_in(Year::new(2015).unwrap()), 25);
+    assert_eq!(calendar.days_in(Year::new(2024).unwrap()), 25);
+    assert_eq!(calendar.days_in(Year::new(2025).unwrap()), 12);
+    assert_eq!(calendar.days(Year::new(2030).unwrap()).count(), 12);
+    assert!(calendar.has_free_part_2(&challenge(2024, 25)));
+    assert!(calendar.has_free_part_2(&challenge(2025, 12)));
+    assert!(!calendar.has_free_part_2(&challenge(2025, 11)));
+}
+
+#[test]
+fn calendar_overrides() {
+    let calendar = Calendar {
+        days: [("2030".to_string(), 10)].into_iter().collect(),
+    };
+
+    assert_eq!(calendar.days_in(Year::new(2029).unwrap()), 12);
+    assert_eq!(calendar.days_in(Year::new(2031).unwrap()), 10);
+    assert!(calendar.check_challenge(challenge(2030, 10)).is_ok());
+    assert!(matches!(
+        calendar.check_challenge(challenge(2030, 11)),
+        Err(RuntimeError::DayOutsideEvent { days: 10, .. })
+    ));
+}
+
+#[test]
+fn unlock_time() {
+    let unlock = Calendar::default().unlock_time(&challenge(2023, 1));
+
+    assert_eq!(unlock.to_rfc3339(), "2023-12-01T00:00:00-05:00");
+}
+
+#[test]
+fn answers_from_output() {
+    let answers = Answers::from_output("Parsing...\nPart 1: 42\nPart 2:  abc \n");
+
+    assert_eq!(answers.part_1.as_deref(), Some("42"));
+    assert_eq!(answers.part_2.as_deref(), Some("abc"));
+    assert_eq!(Answers::from_table(&answers.to_table()), answers);
+    assert_eq!(Answers::from_output("nothing"), Answers::default());
+}
+
+#[test]
+fn count_passed_tests() {
+    let output = "
+running 2 tests
+..
+test result: ok. 2 passed; 0 failed; 0 ignored; 0 measured; 0 filtered out; finished in 0.00s
+
+running 0 tests
+
+test result: ok. 0 passed; 0 failed; 0 ignored; 0 measured; 0 filtered out; finished in 0.00s
+
+running 1 test
+.
+test result: ok. 1 passed; 0 failed; 0 ignored; 0 measured; 0 filtered out; finished in 0.00s
+";
+
+    assert_eq!(runner::count_passed_tests(output), 3);
+    assert_eq!(runner::count_passed_tests(""), 0);
+}
+
+#[test]
+fn check_input() {
+    assert!(validate::check_input("1 2\n3 4\n").is_ok());
+    assert!(validate::check_input("\n").is_err());
+    assert!(validate::check_input("<!DOCTYPE html><html></html>").is_err());
+    assert!(validate::check_input("Puzzle inputs differ by user.  Please log in.").is_err());
+    assert!(validate::check_input("404 Not Found\n").is_err());
+    assert!(validate::check_input("500 12 34\n404 5\n").is_ok());
+}
+
+#[test]
+fn logged_in_user() {
+    let html = r#"<div class="user">someone <span class="star-count">42*</span></div>
+<a href="/auth/logout">[Log Out]</a>"#;
+
+    assert_eq!(validate::logged_in_user(html).as_deref(), Some("someone"));
+    assert_eq!(validate::logged_in_user("<a>[Log In]</a>"), None);
+}
+
+#[test]
+fn migrate_main_function() {
+    let source = r#"const INPUT: &str = include_str!("input.txt");
+
+fn main() {
+    let input = &aoc_utils::input!();
+    part_1(input);
+    // part_2(&input);
+}
+
+fn part_1(input: &str) -> u64 {
+    input.len() as u64
+}
+"#;
+
+    assert_eq!(
+        migrate_source(source).unwrap().unwrap(),
+        r#"
+aoc_utils::main!(part_1);
+
+fn part_1(input: &str) -> u64 {
+    input.len() as u64
+}
+"#
+    );
+}
+
+#[test]
+fn migrate_leaves_current_layout() {
+    assert_eq!(migrate_source("aoc_utils::main!(part_1);\n"), Ok(None));
+    assert!(migrate_source("fn main() {\n    println!(\"hi\");\n}\n").is_err());
+}
+
+#[test]
+fn migrate_requires_the_input() {
+    let migrated = |main: &str| migrate_source(&format!("fn main() {{\n{main}}}\n"));
+
+    assert_eq!(
+        migrated("    part_1(INPUT);\n    part_2(INPUT);\n"),
+        Ok(Some("aoc_utils::main!(part_1, part_2);\n".to_string()))
+    );
+    assert!(migrated("    part_1(EXAMPLE);\n    part_2(EXAMPLE_4);\n").is_err());
+    assert!(migrated("    let input = EXAMPLE;\n    part_1(input);\n").is_err());
+    assert!(migrated("    part_1(&aoc_utils::input!());\n    part_2(EXAMPLE);\n").is_err());
+    assert!(migrated(
+        "    let input = &aoc_utils::input!();\n    let input = EXAMPLE;\n    part_1(input);\n"
+    )
+    .is_err());
+}
+
+#[test]
+fn verdicts() {
+    let page = |message: &str| format!("<main><article><p>{message}</p></article></main>");
+
+    assert_eq!(
+        Verdict::from_html(&page(
+            "That's the right answer!  You are <em>one gold star</em> closer."
+        )),
+        Verdict::Correct
+    );
+    assert_eq!(
+        Verdict::from_html(&page(
+            "That's not the right answer; your answer is too high."
+        )),
+        Verdict::Wrong(Some(Hint::TooHigh))
+    );
+    assert_eq!(
+        Verdict::from_html(&page("That's not the right answer.  If you're stuck...")),
+        Verdict::Wrong(None)
+    );
+    assert_eq!(
+        Verdict::from_html(&page(
+            "You gave an answer too recently. You have 42s left to wait."
+        )),
+        Verdict::TooSoon("42s".to_string())
+    );
+    assert_eq!(
+        Verdict::from_html(&page("You don't seem to be solving the right level.")),
+        Verdict::WrongLevel
+    );
+}
+
+#[test]
+fn guesses_rule_out_answers() {
+    let mut guesses = Guesses::default();
+    guesses.record(
+        1,
+        Guess {
+            answer: "100".to_string(),
+            hint: Some(Hint::TooHigh),
+        },
+    );
+    guesses.record(
+        1,
+        Guess {
+            answer: "10".to_string(),
+            hint: Some(Hint::TooLow),
+        },
+    );
+    guesses.record(
+        2,
+        Guess {
+            answer: "abc".to_string(),
+            hint: None,
+        },
+    );
+
+    assert!(guesses.rules_out(1, "100").is_some());
+    assert!(guesses.rules_out(1, "150").is_some());
+    assert!(guesses.rules_out(1, "5").is_some());
+    assert!(guesses.rules_out(1, "50").is_none());
+    assert!(guesses.rules_out(2, "abc").is_some());
+    assert!(guesses.rules_out(2, "100").is_none());
+}
@@ -0,0 +1,124 @@
+use std::path::Path;
+
+use aoc_manager::{config::Config, Challenge, Day, DayCrate, RuntimeError, Workspace, Year};
+
+const MANIFEST: &str = r#"[workspace]
+resolver = "2"
+members = [
+    # Shared code
+    "aoc-utils",
+    "day_2023_01",
+]
+"#;
+
+fn challenge(year: i32, day: u32) -> Challenge {
+    Challenge::new(Day::new(day).unwrap(), Year::new(year).unwrap())
+}
+
+fn workspace() -> (tempfile::TempDir, Config) {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(dir.path().join("Cargo.toml"), MANIFEST).unwrap();
+    let config = Config::default().with_root(dir.path());
+
+    (dir, config)
+}
+
+fn manifest(root: &Path) -> String {
+    std::fs::read_to_string(root.join("Cargo.toml")).unwrap()
+}
+
+#[test]
+fn add_day_keeps_members_sorted_and_comments() {
+    let (dir, config) = workspace();
+
+    let mut workspace = Workspace::open(dir.path()).unwrap();
+    workspace.add_day(challenge(2022, 5), &config).unwrap();
+    workspace.write().unwrap();
+
+    assert_eq!(
+        manifest(dir.path()),
+        r#"[workspace]
+resolver = "2"
+members = [
+    # Shared code
+    "aoc-utils",
+    "day_2022_05",
+    "day_2023_01",
+]
+"#
+    );
+}
+
+#[test]
+fn add_day_twice_fails() {
+    let (dir, config) = workspace();
+
+    let mut workspace = Workspace::open(dir.path()).unwrap();
+
+    assert!(matches!(
+        workspace.add_day(challenge(2023, 1), &config),
+        Err(RuntimeError::DayAlreadyPresent(_))
+    ));
+}
+
+#[test]
+fn remove_and_rename_members() {
+    let (dir, _) = workspace();
+
+    let mut workspace = Workspace::open(dir.path()).unwrap();
+    workspace.rename("aoc-utils", "utils").unwrap();
+    workspace.remove("day_2023_01").unwrap();
+    workspace.write().unwrap();
+
+    assert_eq!(
+        manifest(dir.path()),
+        r#"[workspace]
+resolver = "2"
+members = [
+    # Shared code
+    "utils",
+]
+"#
+    );
+
+    let mut workspace = Workspace::open(dir.path()).unwrap();
+    assert!(matches!(
+        workspace.remove("day_2023_01"),
+        Err(RuntimeError::MemberNotPresent(_))
+    ));
+}
+
+#[test]
+fn get_days_ignores_other_members() {
+    let (dir, config) = workspace();
+
+    let workspace = Workspace::open(dir.path()).unwrap();
+
+    assert_eq!(workspace.get_days(&config), [challenge(2023, 1)]);
+}
+
+#[test]
+fn day_crate_pending_fetch() {
+    let (dir, config) = workspace();
+    let crate_dir = dir.path().join("day_2023_01");
+    std::fs::create_dir(&crate_dir).unwrap();
+    std::fs::write(
+        crate_dir.join("Cargo.toml"),
+        "[package]\nname = \"day_2023_01\"\nversion = \"0.1.0\"\n",
+    )
+    .unwrap();
+
+    let mut day_crate = DayCrate::new(challenge(2023, 1), &config).unwrap();
+    assert!(!day_crate.is_pending_fetch());
+    day_crate.set_pending_fetch(true);
+    day_crate.write().unwrap();
+
+    let mut day_crate = DayCrate::new(challenge(2023, 1), &config).unwrap();
+    assert!(day_crate.is_pending_fetch());
+    day_crate.set_pending_fetch(false);
+    day_crate.write().unwrap();
+
+    assert!(!DayCrate::new(challenge(2023, 1), &config)
+        .unwrap()
+        .is_pending_fetch());
+}
@@ -0,0 +1,319 @@
+use std::ops::RangeInclusive;
+
+/// The compression of an axis around a set of interesting coordinates, such as
+/// the corners of a huge shape, into few cells: each coordinate gets a cell of
+/// its own, and each gap between two consecutive coordinates is a single cell
+/// too. Cell `2 * i` holds the `i`-th smallest coordinate, and cell `2 * i + 1`
+/// the gap after it.
+///
+/// Each cell has a weight, its size on the original axis by default, so that
+/// lengths measured in cells can be turned back into exact ones. Weights can be
+/// changed, such as to expand the empty space between the coordinates.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Axis {
+    coordinates: Vec<i64>,
+    weights: Vec<i64>,
+    /// The sum of the weights of the cells before each cell, and after the
+    /// last one.
+    offsets: Vec<i64>,
+}
+
+impl Axis {
+    /// Compresses an axis around coordinates, in any order and repeated or
+    /// not.
+    pub fn new(coordinates: impl IntoIterator<Item = i64>) -> Self {
+        let mut coordinates = coordinates.into_iter().collect::<Vec<_>>();
+        coordinates.sort_unstable();
+        coordinates.dedup();
+
+        let weights = (0..(2 * coordinates.len()).saturating_sub(1))
+            .map(|cell| {
+                if cell.is_multiple_of(2) {
+                    1
+                } else {
+                    coordinates[cell / 2 + 1] - coordinates[cell / 2] - 1
+                }
+            })
+            .collect();
+
+        let mut axis = Self {
+            coordinates,
+            weights,
+            offsets: Vec::new(),
+        };
+        axis.update_offsets();
+
+        axis
+    }
+
+    /// Adds the coordinates just before the smallest one and after the largest
+    /// one, so that the cells of the coordinates are surrounded on both
+    /// sides, to flood fill around them.
+    pub fn padded(self) -> Self {
+        let (Some(&first), Some(&last)) = (self.coordinates.first(), self.coordinates.last())
+        else {
+            return self;
+        };
+
+        Self::new(
+            self.coordinates
+                .iter()
+                .copied()
+                .chain([first - 1, last + 1]),
+        )
+    }
+
+    /// Returns the number of cells.
+    pub fn len(&self) -> usize {
+        self.weights.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.weights.is_empty()
+    }
+
+    /// Returns the coordinates the axis was compressed around, sorted.
+    pub fn coordinates(&self) -> &[i64] {
+        &self.coordinates
+    }
+
+    /// Returns the cell of one of the coordinates the axis was compressed
+    /// around.
+    pub fn index(&self, coordinate: i64) -> Option<usize> {
+        self.coordinates
+            .binary_search(&coordinate)
+            .ok()
+            .map(|i| 2 * i)
+    }
+
+    /// Returns the cell containing any coordinate, or `None` if it is outside
+    /// of the compressed coordinates.
+    pub fn cell(&self, coordinate: i64) -> Option<usize> {
+        match self.coordinates.binary_search(&coordinate) {
+            Ok(i) => Some(2 * i),
+            Err(i) if i > 0 && i < self.coordinates.len() => Some(2 * i - 1),
+            Err(_) => None,
+        }
+    }
+
+    /// Returns the coordinates of a cell on the original axis, which are empty
+    /// for the gap between two consecutive coordinates.
+    pub fn range(&self, cell: usize) -> RangeInclusive<i64> {
+        let coordinate = self.coordinates[cell / 2];
+
+        if cell.is_multiple_of(2) {
+            coordinate..=coordinate
+        } else {
+            coordinate + 1..=self.coordinates[cell / 2 + 1] - 1
+        }
+    }
+
+    pub fn weight(&self, cell: usize) -> i64 {
+        self.weights[cell]
+    }
+
+    pub fn set_weight(&mut self, cell: usize, weight: i64) {
+        self.weights[cell] = weight;
+        self.update_offsets();
+    }
+
+    /// Multiplies the weights of the gaps between the coordinates by a factor,
+    /// as if the empty space between them was expanded.
+    pub fn scale_gaps(&mut self, factor: i64) {
+        for weight in self.weights.iter_mut().skip(1).step_by(2) {
+            *weight *= factor;
+        }
+        self.update_offsets();
+    }
+
+    /// Returns the sum of the weights of the cells before a cell, which is its
+    /// position on the original axis relative to the first coordinate, or on
+    /// the expanded axis if the weights were changed. `len()` is accepted,
+    /// giving the total weight.
+    pub fn offset(&self, cell: usize) -> i64 {
+        self.offsets[cell]
+    }
+
+    /// Returns the distance between two cells, as the weight of the cells from
+    /// one to the other, not counting the last one.
+    pub fn distance(&self, a: usize, b: usize) -> i64 {
+        (self.offsets[b] - self.offsets[a]).abs()
+    }
+
+    /// Returns the total weight of a range of cells.
+    pub fn span(&self, cells: RangeInclusive<usize>) -> i64 {
+        self.offsets[cells.end() + 1] - self.offsets[*cells.start()]
+    }
+
+    fn update_offsets(&mut self) {
+        self.offsets = std::iter::once(0)
+            .chain(self.weights.iter().scan(0, |total, weight| {
+                *total += weight;
+                Some(*total)
+            }))
+            .collect();
+    }
+}
+
+/// The compression of a plane around a set of points, as an [`Axis`] for each
+/// coordinate. Cells are `(x, y)` pairs of indices on each axis, whose weights
+/// multiply into their area.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Compression {
+    pub x: Axis,
+    pub y: Axis,
+}
+
+impl Compression {
+    pub fn new(points: impl IntoIterator<Item = (i64, i64)>) -> Self {
+        let (xs, ys): (Vec<_>, Vec<_>) = points.into_iter().unzip();
+
+        Self {
+            x: Axis::new(xs),
+            y: Axis::new(ys),
+        }
+    }
+
+    /// Pads both axes, see [`Axis::padded`].
+    pub fn padded(self) -> Self {
+        Self {
+            x: self.x.padded(),
+            y: self.y.padded(),
+        }
+    }
+
+    /// Returns the numbers of cells on each axis, to allocate a grid of cells
+    /// with `height` rows of `width` cells.
+    pub fn dimensions(&self) -> (usize, usize) {
+        (self.x.len(), self.y.len())
+    }
+
+    /// Returns the cell of one of the points the plane was compressed around.
+    pub fn index(&self, (x, y): (i64, i64)) -> Option<(usize, usize)> {
+        Some((self.x.index(x)?, self.y.index(y)?))
+    }
+
+    /// Returns the cell containing any point, or `None` if it is outside of
+    /// the compressed coordinates.
+    pub fn cell(&self, (x, y): (i64, i64)) -> Option<(usize, usize)> {
+        Some((self.x.cell(x)?, self.y.cell(y)?))
+    }
+
+    /// Returns the area of a cell, from the weights of its axes.
+    pub fn area(&self, (x, y): (usize, usize)) -> i64 {
+        self.x.weight(x) * self.y.weight(y)
+    }
+
+    /// Returns the Manhattan distance between two cells, from the weights of
+    /// the axes.
+    pub fn distance(&self, a: (usize, usize), b: (usize, usize)) -> i64 {
+        self.x.distance(a.0, b.0) + self.y.distance(a.1, b.1)
+    }
+
+    /// Returns the cells of the rectangle with corners `a` and `b`, both
+    /// included, which is a segment when they are on the same row or column,
+    /// such as to draw a path between points.
+    pub fn rectangle(a: (usize, usize), b: (usize, usize)) -> impl Iterator<Item = (usize, usize)> {
+        let (x_min, x_max) = (a.0.min(b.0), a.0.max(b.0));
+        let (y_min, y_max) = (a.1.min(b.1), a.1.max(b.1));
+
+        (y_min..=y_max).flat_map(move |y| (x_min..=x_max).map(move |x| (x, y)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const IMAGE: &str = "...#......
+.......#..
+#.........
+..........
+......#...
+.#........
+.........#
+..........
+.......#..
+#...#.....";
+
+    /// Returns the sum of the distances between the galaxies once each empty
+    /// row and column is `factor` times larger.
+    fn galaxy_distances(factor: i64) -> i64 {
+        let galaxies = IMAGE
+            .lines()
+            .enumerate()
+            .flat_map(|(y, line)| {
+                line.match_indices('#')
+                    .map(move |(x, _)| (x as i64, y as i64))
+            })
+            .collect::<Vec<_>>();
+        let mut compression = Compression::new(galaxies.iter().copied());
+        compression.x.scale_gaps(factor);
+        compression.y.scale_gaps(factor);
+
+        let cells = galaxies
+            .iter()
+            .map(|&galaxy| compression.index(galaxy).unwrap())
+            .collect::<Vec<_>>();
+
+        cells
+            .iter()
+            .enumerate()
+            .flat_map(|(i, &a)| cells[i + 1..].iter().map(move |&b| (a, b)))
+            .map(|(a, b)| compression.distance(a, b))
+            .sum()
+    }
+
+    #[test]
+    fn cosmic_expansion() {
+        assert_eq!(galaxy_distances(1), 292);
+        assert_eq!(galaxy_distances(2), 374);
+        assert_eq!(galaxy_distances(10), 1030);
+        assert_eq!(galaxy_distances(100), 8410);
+    }
+
+    #[test]
+    fn scaled_gaps() {
+        let mut axis = Axis::new([10, 0, 4, 10]);
+        assert_eq!(axis.coordinates(), [0, 4, 10]);
+        assert_eq!(axis.len(), 5);
+        assert_eq!((axis.weight(1), axis.weight(3)), (3, 5));
+        assert_eq!(axis.span(0..=4), 11);
+
+        axis.scale_gaps(10);
+        assert_eq!((axis.weight(1), axis.weight(3)), (30, 50));
+        assert_eq!(
+            (0..=axis.len())
+                .map(|cell| axis.offset(cell))
+                .collect::<Vec<_>>(),
+            [0, 1, 31, 32, 82, 83]
+        );
+        assert_eq!(axis.span(0..=4), 83);
+        assert_eq!(axis.span(1..=3), 81);
+        assert_eq!(axis.distance(4, 0), 82);
+
+        axis.set_weight(2, 0);
+        assert_eq!(axis.span(0..=4), 82);
+    }
+
+    #[test]
+    fn cells() {
+        let axis = Axis::new([0, 4, 10]).padded();
+        assert_eq!(axis.coordinates(), [-1, 0, 4, 10, 11]);
+        assert_eq!(axis.index(4), Some(4));
+        assert_eq!(axis.index(5), None);
+        assert_eq!(axis.cell(5), Some(5));
+        assert_eq!(axis.cell(12), None);
+        assert_eq!(axis.range(5), 5..=9);
+        assert_eq!(axis.range(4), 4..=4);
+
+        let compression = Compression::new([(0, 0), (4, 10)]);
+        assert_eq!(compression.dimensions(), (3, 3));
+        assert_eq!(compression.area((1, 1)), 27);
+        assert_eq!(compression.cell((2, 2)), Some((1, 1)));
+        assert_eq!(
+            Compression::rectangle((2, 1), (1, 0)).collect::<Vec<_>>(),
+            [(1, 0), (2, 0), (1, 1), (2, 1)]
+        );
+    }
+}
@@ -0,0 +1,76 @@
+use std::path::{Path, PathBuf};
+
+use toml::Table;
+
+/// Name of the configuration file of `aoc-manager`, found at the root of the
+/// workspace and in the user's configuration directory.
+pub const CONFIG_FILE: &str = "aoc.toml";
+
+/// Default pattern of the names of the day crates.
+pub const DEFAULT_CRATE_NAME: &str = "day_{year}_{day}";
+
+/// Returns the configuration files of the workspace at `root`, by increasing
+/// order of precedence: the workspace's `aoc.toml`, then the user's
+/// `$XDG_CONFIG_HOME/aoc/aoc.toml` (`~/.config/aoc/aoc.toml` by default).
+pub fn config_files(root: &Path) -> Vec<PathBuf> {
+    let user_dir = std::env::var_os("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .or_else(|| std::env::var_os("HOME").map(|home| Path::new(&home).join(".config")));
+
+    std::iter::once(root.join(CONFIG_FILE))
+        .chain(user_dir.map(|dir| dir.join("aoc").join(CONFIG_FILE)))
+        .collect()
+}
+
+/// Reads a string value of the configuration, given as the path of the key in
+/// the TOML tables (e.g. `["paths", "cache"]`). Files that can't be read are
+/// ignored.
+pub(crate) fn config_value(root: &Path, key: &[&str]) -> Option<String> {
+    config_files(root)
+        .into_iter()
+        .rev()
+        .filter_map(|path| std::fs::read_to_string(path).ok()?.parse::<Table>().ok())
+        .find_map(|table| {
+            let (last, tables) = key.split_last()?;
+            let table = tables
+                .iter()
+                .try_fold(&table, |table, key| table.get(*key)?.as_table())?;
+
+            table.get(*last)?.as_str().map(str::to_string)
+        })
+}
+
+/// Formats the name of the crate of a day, following `pattern` in which
+/// `{year}` and `{day}` are placeholders.
+pub fn format_crate_name(pattern: &str, year: i32, day: u32) -> String {
+    pattern
+        .replace("{year}", &year.to_string())
+        .replace("{day}", &format!("{day:02}"))
+}
+
+/// Extracts the year and day from the name of a crate following `pattern`, in
+/// which `{year}` and `{day}` are placeholders. The two placeholders must be
+/// separated by text that doesn't start with a digit.
+pub fn parse_crate_name(pattern: &str, name: &str) -> Option<(i32, u32)> {
+    let year_first = pattern.find("{year}")? < pattern.find("{day}")?;
+    let (first, second) = if year_first {
+        ("{year}", "{day}")
+    } else {
+        ("{day}", "{year}")
+    };
+
+    let (prefix, rest) = pattern.split_once(first)?;
+    let (middle, suffix) = rest.split_once(second)?;
+
+    let values = name.strip_prefix(prefix)?.strip_suffix(suffix)?;
+    let (a, b) = values.split_at(values.find(|c: char| !c.is_ascii_digit())?);
+    let b = b.strip_prefix(middle)?;
+
+    if middle.is_empty() || a.is_empty() || b.is_empty() || !b.bytes().all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+
+    let (year, day) = if year_first { (a, b) } else { (b, a) };
+
+    Some((year.parse().ok()?, day.parse().ok()?))
+}
@@ -0,0 +1,345 @@
+use std::{
+    borrow::Borrow,
+    collections::{hash_map, HashMap},
+    hash::Hash,
+    ops::{Add, AddAssign, Sub, SubAssign},
+};
+
+/// A multiset, counting how many times each item occurs. Items with a count of
+/// zero are never kept.
+#[derive(Debug, Clone)]
+pub struct Counter<T> {
+    counts: HashMap<T, usize>,
+}
+
+impl<T> Default for Counter<T> {
+    fn default() -> Self {
+        Self {
+            counts: HashMap::new(),
+        }
+    }
+}
+
+impl<T: Hash + Eq> PartialEq for Counter<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.counts == other.counts
+    }
+}
+
+impl<T: Hash + Eq> Eq for Counter<T> {}
+
+impl<T: Hash + Eq> Counter<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a counter from items and their counts, adding up the counts of
+    /// repeated items.
+    pub fn from_counts(counts: impl IntoIterator<Item = (T, usize)>) -> Self {
+        let mut counter = Self::new();
+        for (item, count) in counts {
+            counter.add_count(item, count);
+        }
+
+        counter
+    }
+
+    pub fn add(&mut self, item: T) {
+        self.add_count(item, 1);
+    }
+
+    pub fn add_count(&mut self, item: T, count: usize) {
+        if count > 0 {
+            *self.counts.entry(item).or_default() += count;
+        }
+    }
+
+    /// Removes `count` occurrences of an item, or all of them if there are
+    /// fewer. Returns how many were removed.
+    pub fn remove_count<Q>(&mut self, item: &Q, count: usize) -> usize
+    where
+        T: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let Some(current) = self.counts.get_mut(item) else {
+            return 0;
+        };
+
+        let removed = count.min(*current);
+        *current -= removed;
+        if *current == 0 {
+            self.counts.remove(item);
+        }
+
+        removed
+    }
+
+    /// Removes every occurrence of an item, and returns its count.
+    pub fn remove_all<Q>(&mut self, item: &Q) -> usize
+    where
+        T: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.counts.remove(item).unwrap_or(0)
+    }
+
+    /// Returns the count of an item, which is 0 if it isn't in the counter.
+    pub fn get<Q>(&self, item: &Q) -> usize
+    where
+        T: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.counts.get(item).copied().unwrap_or(0)
+    }
+
+    pub fn contains<Q>(&self, item: &Q) -> bool
+    where
+        T: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.counts.contains_key(item)
+    }
+
+    /// Returns the number of distinct items.
+    pub fn len(&self) -> usize {
+        self.counts.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.counts.is_empty()
+    }
+
+    /// Returns the number of items, counting repeated ones.
+    pub fn total(&self) -> usize {
+        self.counts.values().sum()
+    }
+
+    /// Returns the distinct items and their counts, in no particular order.
+    pub fn iter(&self) -> impl Iterator<Item = (&T, usize)> {
+        self.counts.iter().map(|(item, &count)| (item, count))
+    }
+
+    pub fn items(&self) -> impl Iterator<Item = &T> {
+        self.counts.keys()
+    }
+
+    /// Returns the counts of the items, from the largest to the smallest,
+    /// which identifies the shape of the multiset whatever its items, such as
+    /// `[3, 2]` for a full house.
+    pub fn signature(&self) -> Vec<usize> {
+        let mut counts = self.counts.values().copied().collect::<Vec<_>>();
+        counts.sort_unstable_by(|a, b| b.cmp(a));
+
+        counts
+    }
+
+    /// Returns the largest count, or 0 for an empty counter.
+    pub fn max_count(&self) -> usize {
+        self.counts.values().copied().max().unwrap_or(0)
+    }
+
+    /// Replaces each item by the items `mapping` gives for it, each counted as
+    /// many times as the original item. Items mapped to nothing disappear.
+    ///
+    /// This makes a step of a simulation where identical items evolve the same
+    /// way, whatever their number.
+    pub fn flat_map<U, I>(&self, mut mapping: impl FnMut(&T) -> I) -> Counter<U>
+    where
+        U: Hash + Eq,
+        I: IntoIterator<Item = U>,
+    {
+        let mut mapped = Counter::new();
+        for (item, &count) in &self.counts {
+            for new_item in mapping(item) {
+                mapped.add_count(new_item, count);
+            }
+        }
+
+        mapped
+    }
+
+    /// Replaces each item by another one, merging the counts of items mapped to
+    /// the same one.
+    pub fn map<U: Hash + Eq>(&self, mut mapping: impl FnMut(&T) -> U) -> Counter<U> {
+        self.flat_map(|item| [mapping(item)])
+    }
+
+    /// Returns the items present in either counter, with the largest of their
+    /// counts.
+    pub fn union(mut self, other: Self) -> Self {
+        for (item, count) in other.counts {
+            let current = self.counts.entry(item).or_default();
+            *current = count.max(*current);
+        }
+
+        self
+    }
+
+    /// Returns the items present in both counters, with the smallest of their
+    /// counts.
+    pub fn intersection(mut self, other: &Self) -> Self {
+        self.counts.retain(|item, count| {
+            *count = other.get(item).min(*count);
+            *count > 0
+        });
+
+        self
+    }
+}
+
+impl<T: Hash + Eq + Ord> Counter<T> {
+    /// Returns the distinct items and their counts, from the most common to
+    /// the least, with ties broken by the smallest item.
+    pub fn most_common(&self) -> Vec<(&T, usize)> {
+        let mut items = self.iter().collect::<Vec<_>>();
+        items.sort_unstable_by(|(a, a_count), (b, b_count)| b_count.cmp(a_count).then(a.cmp(b)));
+
+        items
+    }
+
+    /// Returns the distinct items and their counts, by order of the items.
+    pub fn sorted(&self) -> Vec<(&T, usize)> {
+        let mut items = self.iter().collect::<Vec<_>>();
+        items.sort_unstable_by_key(|&(item, _)| item);
+
+        items
+    }
+}
+
+impl<T: Hash + Eq> FromIterator<T> for Counter<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(items: I) -> Self {
+        let mut counter = Self::new();
+        counter.extend(items);
+
+        counter
+    }
+}
+
+impl<T: Hash + Eq> Extend<T> for Counter<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, items: I) {
+        for item in items {
+            self.add(item);
+        }
+    }
+}
+
+impl<T: Hash + Eq> IntoIterator for Counter<T> {
+    type Item = (T, usize);
+    type IntoIter = hash_map::IntoIter<T, usize>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.counts.into_iter()
+    }
+}
+
+impl<T: Hash + Eq> AddAssign for Counter<T> {
+    fn add_assign(&mut self, other: Self) {
+        for (item, count) in other.counts {
+            self.add_count(item, count);
+        }
+    }
+}
+
+impl<T: Hash + Eq> Add for Counter<T> {
+    type Output = Self;
+
+    fn add(mut self, other: Self) -> Self {
+        self += other;
+        self
+    }
+}
+
+/// Removes the occurrences of the other counter, keeping counts at zero or
+/// more.
+impl<T: Hash + Eq> SubAssign for Counter<T> {
+    fn sub_assign(&mut self, other: Self) {
+        for (item, count) in other.counts {
+            self.remove_count(&item, count);
+        }
+    }
+}
+
+impl<T: Hash + Eq> Sub for Counter<T> {
+    type Output = Self;
+
+    fn sub(mut self, other: Self) -> Self {
+        self -= other;
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn blink(stone: &u64) -> Vec<u64> {
+        let digits = stone.to_string();
+
+        if *stone == 0 {
+            vec![1]
+        } else if digits.len().is_multiple_of(2) {
+            let (left, right) = digits.split_at(digits.len() / 2);
+            vec![left.parse().unwrap(), right.parse().unwrap()]
+        } else {
+            vec![stone * 2024]
+        }
+    }
+
+    #[test]
+    fn plutonian_pebbles() {
+        let mut stones = [125, 17].into_iter().collect::<Counter<u64>>();
+
+        for _ in 0..25 {
+            stones = stones.flat_map(blink);
+        }
+        assert_eq!(stones.total(), 55312);
+
+        for _ in 25..75 {
+            stones = stones.flat_map(blink);
+        }
+        assert_eq!(stones.total(), 65601038650482);
+        assert!(stones.len() < 4000);
+    }
+
+    #[test]
+    fn hands() {
+        let signature = |hand: &str| hand.chars().collect::<Counter<_>>().signature();
+
+        assert_eq!(signature("32T3K"), [2, 1, 1, 1]);
+        assert_eq!(signature("KTJJT"), [2, 2, 1]);
+        assert_eq!(signature("QQQJA"), [3, 1, 1]);
+        assert_eq!(signature("AAAAA"), [5]);
+
+        let hand = "KTJJT".chars().collect::<Counter<_>>();
+        assert_eq!(hand.most_common(), [(&'J', 2), (&'T', 2), (&'K', 1)]);
+        assert_eq!(hand.sorted(), [(&'J', 2), (&'K', 1), (&'T', 2)]);
+        assert_eq!(hand.max_count(), 2);
+        assert_eq!(hand.map(|card| *card == 'J').get(&true), 2);
+    }
+
+    #[test]
+    fn arithmetic() {
+        let mut a = Counter::from_counts([("x", 3), ("y", 1), ("x", 1), ("z", 0)]);
+        let b = Counter::from_counts([("x", 2), ("y", 5)]);
+
+        assert_eq!((a.get("x"), a.len(), a.contains("z")), (4, 2, false));
+        assert_eq!(
+            a.clone() + b.clone(),
+            Counter::from_counts([("x", 6), ("y", 6)])
+        );
+        assert_eq!(a.clone() - b.clone(), Counter::from_counts([("x", 2)]));
+        assert_eq!(
+            a.clone().union(b.clone()),
+            Counter::from_counts([("x", 4), ("y", 5)])
+        );
+        assert_eq!(
+            a.clone().intersection(&b),
+            Counter::from_counts([("x", 2), ("y", 1)])
+        );
+
+        assert_eq!(a.remove_count("x", 10), 4);
+        assert_eq!(a.remove_count("y", 0), 0);
+        assert_eq!(a.remove_all("y"), 1);
+        assert!(a.is_empty());
+    }
+}
@@ -0,0 +1,118 @@
+use std::path::{Path, PathBuf};
+
+use chacha20poly1305::{
+    aead::{Aead, KeyInit},
+    ChaCha20Poly1305, Key, Nonce,
+};
+use sha2::{Digest, Sha256};
+use thiserror::Error;
+
+use crate::config::config_value;
+
+/// Environment variable holding the key of the encrypted cache. It takes
+/// precedence over the key file.
+pub const CACHE_KEY_VAR: &str = "AOC_CACHE_KEY";
+
+/// Default file, relative to the workspace root, holding the key of the
+/// encrypted cache. It can be changed with the `paths.cache_key` key of the
+/// configuration.
+pub const DEFAULT_CACHE_KEY_PATH: &str = ".aoc-cache-key";
+
+/// Extension appended to the name of a cached file to get the name of its
+/// encrypted copy.
+pub const ENCRYPTED_EXTENSION: &str = "enc";
+
+/// Header of the encrypted files, which identifies the format.
+const MAGIC: &[u8] = b"aoc-cache-v1\n";
+
+const NONCE_LEN: usize = 12;
+
+#[derive(Debug, Error)]
+pub enum DecryptionError {
+    #[error("the file isn't an encrypted cache file")]
+    InvalidFormat,
+    #[error("the file was encrypted with another key, or was modified")]
+    WrongKey,
+}
+
+/// The key of the encrypted cache, derived from a passphrase of any length,
+/// such as the content of the key file.
+///
+/// Files are encrypted with ChaCha20-Poly1305, with a nonce derived from the
+/// key and the content. Encrypting the same content twice thus gives the same
+/// file, so that re-encrypting the cache doesn't change the files committed to
+/// git.
+///
+/// The key is a single unsalted SHA-256 of the passphrase, which is fast
+/// enough to decrypt an input at each run of a solution, even in debug
+/// builds, but does nothing to slow down guessing. The passphrase must thus
+/// be random and long, such as 32 random bytes encoded in base64, rather than
+/// something memorable: anyone can try to guess it from the committed files.
+pub struct CacheKey([u8; 32]);
+
+impl CacheKey {
+    pub fn from_passphrase(passphrase: &str) -> Self {
+        Self(Sha256::digest(passphrase.trim().as_bytes()).into())
+    }
+
+    /// Loads the key from the [`CACHE_KEY_VAR`] environment variable, or else
+    /// from `path`. Returns `None` if neither is set.
+    pub fn load(path: &Path) -> std::io::Result<Option<Self>> {
+        if let Ok(passphrase) = std::env::var(CACHE_KEY_VAR) {
+            return Ok(Some(Self::from_passphrase(&passphrase)));
+        }
+
+        match std::fs::read_to_string(path) {
+            Ok(passphrase) => Ok(Some(Self::from_passphrase(&passphrase))),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
+    pub fn encrypt(&self, plaintext: &[u8]) -> Vec<u8> {
+        let digest = Sha256::new()
+            .chain_update(self.0)
+            .chain_update(plaintext)
+            .finalize();
+        let nonce = Nonce::from_slice(&digest[..NONCE_LEN]);
+        let ciphertext = self
+            .cipher()
+            .encrypt(nonce, plaintext)
+            .expect("encryption into a vector should not fail");
+
+        [MAGIC, nonce.as_slice(), &ciphertext].concat()
+    }
+
+    pub fn decrypt(&self, data: &[u8]) -> Result<Vec<u8>, DecryptionError> {
+        let data = data
+            .strip_prefix(MAGIC)
+            .filter(|data| data.len() >= NONCE_LEN)
+            .ok_or(DecryptionError::InvalidFormat)?;
+        let (nonce, ciphertext) = data.split_at(NONCE_LEN);
+
+        self.cipher()
+            .decrypt(Nonce::from_slice(nonce), ciphertext)
+            .map_err(|_| DecryptionError::WrongKey)
+    }
+
+    fn cipher(&self) -> ChaCha20Poly1305 {
+        ChaCha20Poly1305::new(Key::from_slice(&self.0))
+    }
+}
+
+/// Returns the path of the encrypted copy of a cached file.
+pub fn encrypted_path(path: &Path) -> PathBuf {
+    let mut name = path.file_name().unwrap_or_default().to_os_string();
+    name.push(".");
+    name.push(ENCRYPTED_EXTENSION);
+
+    path.with_file_name(name)
+}
+
+/// Returns the path of the key file of the workspace at `root`, as configured.
+pub(crate) fn cache_key_path(root: &Path) -> PathBuf {
+    root.join(
+        config_value(root, &["paths", "cache_key"])
+            .unwrap_or_else(|| DEFAULT_CACHE_KEY_PATH.to_string()),
+    )
+}
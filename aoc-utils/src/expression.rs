@@ -0,0 +1,762 @@
+use std::{fmt, str::FromStr};
+
+use thiserror::Error;
+
+use crate::sequence::Rational;
+
+#[derive(Debug, Error, Clone, PartialEq, Eq)]
+pub enum ExpressionError {
+    #[error("unexpected \"{found}\" at position {position}")]
+    UnexpectedToken { position: usize, found: String },
+    #[error("unexpected end of expression")]
+    UnexpectedEnd,
+    #[error("no value for the variable \"{}\"", .0)]
+    UnknownVariable(String),
+    #[error("division by zero")]
+    DivisionByZero,
+    #[error("integer overflow")]
+    Overflow,
+    #[error("the modulus must be positive, not {}", .0)]
+    InvalidModulus(i64),
+    #[error("{value} has no inverse modulo {modulus}")]
+    NotInvertible { value: i64, modulus: i64 },
+    #[error("the equation isn't linear in \"{}\"", .0)]
+    NotLinear(String),
+    #[error("the equation has no solution")]
+    NoSolution,
+    #[error("every value is a solution of the equation")]
+    InfiniteSolutions,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum BinaryOp {
+    Add,
+    Sub,
+    Mul,
+    /// Integer division, rounding towards zero.
+    Div,
+}
+
+impl BinaryOp {
+    fn symbol(&self) -> char {
+        match self {
+            Self::Add => '+',
+            Self::Sub => '-',
+            Self::Mul => '*',
+            Self::Div => '/',
+        }
+    }
+
+    fn precedence(&self) -> u8 {
+        match self {
+            Self::Add | Self::Sub => 1,
+            Self::Mul | Self::Div => 2,
+        }
+    }
+}
+
+/// An arithmetic expression over integers, with variables, such as
+/// `old * old` or `(a + 3) / b`. Variables are names made of letters, digits
+/// and underscores, not starting with a digit.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Expr {
+    Number(i64),
+    Variable(String),
+    Neg(Box<Expr>),
+    Binary(BinaryOp, Box<Expr>, Box<Expr>),
+}
+
+impl Expr {
+    pub fn binary(op: BinaryOp, left: Expr, right: Expr) -> Self {
+        Self::Binary(op, Box::new(left), Box::new(right))
+    }
+
+    /// Returns the names of the variables of the expression, in order of
+    /// appearance and without repetitions.
+    pub fn variables(&self) -> Vec<&str> {
+        let mut variables = Vec::new();
+        self.collect_variables(&mut variables);
+
+        variables
+    }
+
+    fn collect_variables<'a>(&'a self, variables: &mut Vec<&'a str>) {
+        match self {
+            Self::Number(_) => {}
+            Self::Variable(name) => {
+                if !variables.contains(&name.as_str()) {
+                    variables.push(name);
+                }
+            }
+            Self::Neg(operand) => operand.collect_variables(variables),
+            Self::Binary(_, left, right) => {
+                left.collect_variables(variables);
+                right.collect_variables(variables);
+            }
+        }
+    }
+
+    /// Evaluates the expression, with the values `variables` gives for its
+    /// variables.
+    pub fn evaluate(
+        &self,
+        variables: impl Fn(&str) -> Option<i64>,
+    ) -> Result<i64, ExpressionError> {
+        self.evaluate_with(&variables)
+    }
+
+    fn evaluate_with(
+        &self,
+        variables: &dyn Fn(&str) -> Option<i64>,
+    ) -> Result<i64, ExpressionError> {
+        match self {
+            Self::Number(value) => Ok(*value),
+            Self::Variable(name) => lookup(variables, name),
+            Self::Neg(operand) => operand
+                .evaluate_with(variables)?
+                .checked_neg()
+                .ok_or(ExpressionError::Overflow),
+            Self::Binary(op, left, right) => {
+                let (left, right) = (
+                    left.evaluate_with(variables)?,
+                    right.evaluate_with(variables)?,
+                );
+
+                match op {
+                    BinaryOp::Add => left.checked_add(right),
+                    BinaryOp::Sub => left.checked_sub(right),
+                    BinaryOp::Mul => left.checked_mul(right),
+                    BinaryOp::Div if right == 0 => return Err(ExpressionError::DivisionByZero),
+                    BinaryOp::Div => left.checked_div(right),
+                }
+                .ok_or(ExpressionError::Overflow)
+            }
+        }
+    }
+
+    /// Evaluates the expression modulo `modulus`, which keeps values small
+    /// when only their remainders matter. Divisions multiply by the modular
+    /// inverse, so they fail if the divisor isn't coprime with the modulus.
+    /// The modulus must be positive, and the result is between 0 and
+    /// `modulus - 1`.
+    pub fn evaluate_mod(
+        &self,
+        variables: impl Fn(&str) -> Option<i64>,
+        modulus: i64,
+    ) -> Result<i64, ExpressionError> {
+        if modulus <= 0 {
+            return Err(ExpressionError::InvalidModulus(modulus));
+        }
+
+        self.evaluate_mod_with(&variables, modulus)
+    }
+
+    fn evaluate_mod_with(
+        &self,
+        variables: &dyn Fn(&str) -> Option<i64>,
+        modulus: i64,
+    ) -> Result<i64, ExpressionError> {
+        let reduce = |value: i128| value.rem_euclid(modulus as i128) as i64;
+
+        match self {
+            Self::Number(value) => Ok(reduce(*value as i128)),
+            Self::Variable(name) => Ok(reduce(lookup(variables, name)? as i128)),
+            Self::Neg(operand) => Ok(reduce(
+                -(operand.evaluate_mod_with(variables, modulus)? as i128),
+            )),
+            Self::Binary(op, left, right) => {
+                let left = left.evaluate_mod_with(variables, modulus)? as i128;
+                let right = right.evaluate_mod_with(variables, modulus)?;
+
+                Ok(reduce(match op {
+                    BinaryOp::Add => left + right as i128,
+                    BinaryOp::Sub => left - right as i128,
+                    BinaryOp::Mul => left * right as i128,
+                    BinaryOp::Div => left * mod_inverse(right, modulus)? as i128,
+                }))
+            }
+        }
+    }
+
+    /// Solves `self = other` for the variable `unknown`, with the values
+    /// `variables` gives for the other variables. Both sides must be linear in
+    /// the unknown, such as when it appears only once. Divisions are exact, so
+    /// the solution can be a fraction.
+    pub fn solve(
+        &self,
+        other: &Expr,
+        unknown: &str,
+        variables: impl Fn(&str) -> Option<i64>,
+    ) -> Result<Rational, ExpressionError> {
+        let left = self.linear(unknown, &variables)?;
+        let right = other.linear(unknown, &variables)?;
+
+        // a * x + b = c * x + d, so x = (d - b) / (a - c)
+        let coefficient = checked(left.coefficient.checked_sub(right.coefficient))?;
+        let constant = checked(right.constant.checked_sub(left.constant))?;
+
+        match (coefficient.is_zero(), constant.is_zero()) {
+            (false, _) => checked(constant.checked_div(coefficient)),
+            (true, true) => Err(ExpressionError::InfiniteSolutions),
+            (true, false) => Err(ExpressionError::NoSolution),
+        }
+    }
+
+    /// Returns the expression as `coefficient * unknown + constant`.
+    fn linear(
+        &self,
+        unknown: &str,
+        variables: &dyn Fn(&str) -> Option<i64>,
+    ) -> Result<Linear, ExpressionError> {
+        Ok(match self {
+            Self::Number(value) => Linear::constant(Rational::from(*value)),
+            Self::Variable(name) if name == unknown => Linear {
+                coefficient: Rational::ONE,
+                constant: Rational::ZERO,
+            },
+            Self::Variable(name) => Linear::constant(lookup(variables, name)?.into()),
+            Self::Neg(operand) => operand.linear(unknown, variables)?.scale(-Rational::ONE)?,
+            Self::Binary(op, left, right) => {
+                let left = left.linear(unknown, variables)?;
+                let right = right.linear(unknown, variables)?;
+                let not_linear = || ExpressionError::NotLinear(unknown.to_string());
+
+                match op {
+                    BinaryOp::Add => left.add(right)?,
+                    BinaryOp::Sub => left.add(right.scale(-Rational::ONE)?)?,
+                    BinaryOp::Mul if left.coefficient.is_zero() => right.scale(left.constant)?,
+                    BinaryOp::Mul if right.coefficient.is_zero() => left.scale(right.constant)?,
+                    BinaryOp::Mul => return Err(not_linear()),
+                    BinaryOp::Div if !right.coefficient.is_zero() => return Err(not_linear()),
+                    BinaryOp::Div if right.constant.is_zero() => {
+                        return Err(ExpressionError::DivisionByZero)
+                    }
+                    BinaryOp::Div => {
+                        left.scale(checked(Rational::ONE.checked_div(right.constant))?)?
+                    }
+                }
+            }
+        })
+    }
+}
+
+/// Turns the result of a checked fraction operation into an error on overflow.
+fn checked(value: Option<Rational>) -> Result<Rational, ExpressionError> {
+    value.ok_or(ExpressionError::Overflow)
+}
+
+fn lookup(variables: &dyn Fn(&str) -> Option<i64>, name: &str) -> Result<i64, ExpressionError> {
+    variables(name).ok_or_else(|| ExpressionError::UnknownVariable(name.to_string()))
+}
+
+/// Returns the inverse of `value` modulo `modulus`, with the extended
+/// Euclidean algorithm.
+fn mod_inverse(value: i64, modulus: i64) -> Result<i64, ExpressionError> {
+    let (mut old_r, mut r) = (value as i128, modulus as i128);
+    let (mut old_s, mut s) = (1i128, 0i128);
+
+    while r != 0 {
+        let quotient = old_r / r;
+        (old_r, r) = (r, old_r - quotient * r);
+        (old_s, s) = (s, old_s - quotient * s);
+    }
+
+    if old_r == 1 {
+        Ok(old_s.rem_euclid(modulus as i128) as i64)
+    } else {
+        Err(ExpressionError::NotInvertible { value, modulus })
+    }
+}
+
+/// A linear function of an unknown.
+#[derive(Debug, Clone, Copy)]
+struct Linear {
+    coefficient: Rational,
+    constant: Rational,
+}
+
+impl Linear {
+    fn constant(constant: Rational) -> Self {
+        Self {
+            coefficient: Rational::ZERO,
+            constant,
+        }
+    }
+
+    fn add(self, other: Self) -> Result<Self, ExpressionError> {
+        Ok(Self {
+            coefficient: checked(self.coefficient.checked_add(other.coefficient))?,
+            constant: checked(self.constant.checked_add(other.constant))?,
+        })
+    }
+
+    fn scale(self, factor: Rational) -> Result<Self, ExpressionError> {
+        Ok(Self {
+            coefficient: checked(self.coefficient.checked_mul(factor))?,
+            constant: checked(self.constant.checked_mul(factor))?,
+        })
+    }
+}
+
+impl FromStr for Expr {
+    type Err = ExpressionError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parser = Parser {
+            tokens: tokenize(s)?,
+            position: 0,
+        };
+
+        let expr = parser.expression(0)?;
+        match parser.tokens.get(parser.position) {
+            Some((position, token)) => Err(ExpressionError::UnexpectedToken {
+                position: *position,
+                found: token.to_string(),
+            }),
+            None => Ok(expr),
+        }
+    }
+}
+
+impl fmt::Display for Expr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Number(value) => write!(f, "{value}"),
+            Self::Variable(name) => write!(f, "{name}"),
+            Self::Neg(operand) => match **operand {
+                Self::Binary(..) => write!(f, "-({operand})"),
+                _ => write!(f, "-{operand}"),
+            },
+            Self::Binary(op, left, right) => {
+                // Parentheses are only needed around operations of lower
+                // precedence, or of equal precedence on the right
+                let needs_parentheses = |expr: &Expr, right_side: bool| match expr {
+                    Self::Binary(inner, ..) => {
+                        inner.precedence() < op.precedence()
+                            || (right_side && inner.precedence() == op.precedence())
+                    }
+                    _ => false,
+                };
+
+                if needs_parentheses(left, false) {
+                    write!(f, "({left})")?;
+                } else {
+                    write!(f, "{left}")?;
+                }
+                write!(f, " {} ", op.symbol())?;
+                if needs_parentheses(right, true) {
+                    write!(f, "({right})")
+                } else {
+                    write!(f, "{right}")
+                }
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Token {
+    Number(i64),
+    Identifier(String),
+    Operator(BinaryOp),
+    Open,
+    Close,
+}
+
+impl fmt::Display for Token {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Number(value) => write!(f, "{value}"),
+            Self::Identifier(name) => write!(f, "{name}"),
+            Self::Operator(op) => write!(f, "{}", op.symbol()),
+            Self::Open => write!(f, "("),
+            Self::Close => write!(f, ")"),
+        }
+    }
+}
+
+/// Splits an expression into tokens, with their positions.
+fn tokenize(s: &str) -> Result<Vec<(usize, Token)>, ExpressionError> {
+    let mut tokens = Vec::new();
+    let mut chars = s.char_indices().peekable();
+
+    while let Some((position, c)) = chars.next() {
+        let token = match c {
+            c if c.is_whitespace() => continue,
+            '+' => Token::Operator(BinaryOp::Add),
+            '-' => Token::Operator(BinaryOp::Sub),
+            '*' => Token::Operator(BinaryOp::Mul),
+            '/' => Token::Operator(BinaryOp::Div),
+            '(' => Token::Open,
+            ')' => Token::Close,
+            c if c.is_ascii_alphanumeric() || c == '_' => {
+                let mut end = position + c.len_utf8();
+                while let Some(&(next, c)) = chars.peek() {
+                    if !(c.is_ascii_alphanumeric() || c == '_') {
+                        break;
+                    }
+                    end = next + c.len_utf8();
+                    chars.next();
+                }
+
+                let word = &s[position..end];
+                if c.is_ascii_digit() {
+                    Token::Number(word.parse().map_err(|_| ExpressionError::UnexpectedToken {
+                        position,
+                        found: word.to_string(),
+                    })?)
+                } else {
+                    Token::Identifier(word.to_string())
+                }
+            }
+            c => {
+                return Err(ExpressionError::UnexpectedToken {
+                    position,
+                    found: c.to_string(),
+                })
+            }
+        };
+
+        tokens.push((position, token));
+    }
+
+    Ok(tokens)
+}
+
+/// A precedence climbing parser over the tokens of an expression.
+struct Parser {
+    tokens: Vec<(usize, Token)>,
+    position: usize,
+}
+
+impl Parser {
+    fn next(&mut self) -> Result<(usize, Token), ExpressionError> {
+        let token = self
+            .tokens
+            .get(self.position)
+            .cloned()
+            .ok_or(ExpressionError::UnexpectedEnd)?;
+        self.position += 1;
+
+        Ok(token)
+    }
+
+    /// Parses operations whose operators have at least the given precedence.
+    fn expression(&mut self, min_precedence: u8) -> Result<Expr, ExpressionError> {
+        let mut left = self.operand()?;
+
+        while let Some((_, Token::Operator(op))) = self.tokens.get(self.position) {
+            let op = *op;
+            if op.precedence() < min_precedence {
+                break;
+            }
+
+            self.position += 1;
+            // Operators are left associative, so the right operand only takes
+            // operators of higher precedence
+            let right = self.expression(op.precedence() + 1)?;
+            left = Expr::binary(op, left, right);
+        }
+
+        Ok(left)
+    }
+
+    fn operand(&mut self) -> Result<Expr, ExpressionError> {
+        match self.next()? {
+            (_, Token::Number(value)) => Ok(Expr::Number(value)),
+            (_, Token::Identifier(name)) => Ok(Expr::Variable(name)),
+            (_, Token::Operator(BinaryOp::Sub)) => Ok(Expr::Neg(Box::new(self.operand()?))),
+            (_, Token::Open) => {
+                let expr = self.expression(0)?;
+                match self.next()? {
+                    (_, Token::Close) => Ok(expr),
+                    (position, token) => Err(ExpressionError::UnexpectedToken {
+                        position,
+                        found: token.to_string(),
+                    }),
+                }
+            }
+            (position, token) => Err(ExpressionError::UnexpectedToken {
+                position,
+                found: token.to_string(),
+            }),
+        }
+    }
+}
+
+/// An operator of the puzzles where operators are inserted between numbers
+/// and evaluated strictly from left to right, ignoring precedence.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Operator {
+    Add,
+    Multiply,
+    /// Writes the digits of the right operand after the left one.
+    Concatenate,
+}
+
+impl Operator {
+    /// Applies the operator, or returns `None` on overflow.
+    pub fn apply(&self, left: u64, right: u64) -> Option<u64> {
+        match self {
+            Self::Add => left.checked_add(right),
+            Self::Multiply => left.checked_mul(right),
+            Self::Concatenate => left.checked_mul(digit_power(right))?.checked_add(right),
+        }
+    }
+
+    /// Returns the left operand that gives `result` with `right`, if there is
+    /// a single one.
+    fn invert(&self, result: u64, right: u64) -> Option<u64> {
+        match self {
+            Self::Add => result.checked_sub(right),
+            Self::Multiply if right != 0 && result.is_multiple_of(right) => Some(result / right),
+            Self::Multiply => None,
+            Self::Concatenate => {
+                let power = digit_power(right);
+                (result % power == right).then_some(result / power)
+            }
+        }
+    }
+}
+
+/// Returns the power of 10 with as many zeros as `value` has digits.
+fn digit_power(value: u64) -> u64 {
+    10u64.saturating_pow(value.checked_ilog10().unwrap_or(0) + 1)
+}
+
+/// Finds operators that make the operands, evaluated from left to right, equal
+/// to `target`, among the allowed `operators`. Returns the operators to put
+/// between the operands, or `None` if there are none.
+///
+/// The search goes backwards from the target, undoing the last operation: an
+/// addition must leave a non-negative value, a multiplication must divide
+/// exactly, and a concatenation must match the last digits. This prunes most
+/// branches early.
+pub fn find_operators(
+    target: u64,
+    operands: &[u64],
+    operators: &[Operator],
+) -> Option<Vec<Operator>> {
+    let (&first, rest) = operands.split_first()?;
+    let mut found = Vec::with_capacity(rest.len());
+
+    search_operators(target, first, rest, operators, &mut found).then_some(found)
+}
+
+/// Pushes the operators found once the search succeeds, which is from the
+/// first one to the last as the innermost call succeeds first.
+fn search_operators(
+    target: u64,
+    first: u64,
+    rest: &[u64],
+    operators: &[Operator],
+    found: &mut Vec<Operator>,
+) -> bool {
+    let Some((&last, rest)) = rest.split_last() else {
+        return target == first;
+    };
+
+    for &operator in operators {
+        let matches = match operator.invert(target, last) {
+            Some(left) => search_operators(left, first, rest, operators, found),
+            // Multiplying by zero gives zero whatever the left operand, which
+            // only has to be reachable
+            None if operator == Operator::Multiply && last == 0 && target == 0 => {
+                any_operators(first, rest, operators)
+                    .map(|operators| found.extend(operators))
+                    .is_some()
+            }
+            None => false,
+        };
+
+        if matches {
+            found.push(operator);
+            return true;
+        }
+    }
+
+    false
+}
+
+/// Finds operators that evaluate the operands without overflowing, whatever
+/// the result.
+fn any_operators(value: u64, rest: &[u64], operators: &[Operator]) -> Option<Vec<Operator>> {
+    let Some((&next, rest)) = rest.split_first() else {
+        return Some(Vec::new());
+    };
+
+    operators.iter().find_map(|&operator| {
+        let mut found = any_operators(operator.apply(value, next)?, rest, operators)?;
+        found.insert(0, operator);
+
+        Some(found)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(text: &str) -> Expr {
+        text.parse().unwrap()
+    }
+
+    #[test]
+    fn display_round_trip() {
+        for (text, displayed) in [
+            ("a - (b - c)", "a - (b - c)"),
+            ("(a - b) - c", "a - b - c"),
+            ("a / (b * c)", "a / (b * c)"),
+            ("(a + b) * (c - 4)", "(a + b) * (c - 4)"),
+            ("-(a + 1) * 2", "-(a + 1) * 2"),
+            ("2 * -3", "2 * -3"),
+            ("((x))", "x"),
+            ("1+2*3", "1 + 2 * 3"),
+        ] {
+            let expr = parse(text);
+
+            assert_eq!(expr.to_string(), displayed);
+            assert_eq!(parse(displayed), expr);
+        }
+    }
+
+    #[test]
+    fn parse_errors() {
+        assert_eq!("a +".parse::<Expr>(), Err(ExpressionError::UnexpectedEnd));
+        assert_eq!(
+            "a + )".parse::<Expr>().unwrap_err().to_string(),
+            "unexpected \")\" at position 4"
+        );
+        assert!("3 $ 4".parse::<Expr>().is_err());
+        assert!("a b".parse::<Expr>().is_err());
+    }
+
+    #[test]
+    fn evaluation() {
+        let old = |name: &str| (name == "old").then_some(79);
+
+        assert_eq!(parse("old * old").evaluate(old), Ok(6241));
+        assert_eq!(parse("old * old").evaluate_mod(old, 96577), Ok(6241));
+        assert_eq!(parse("10 / 4 * 3").evaluate(|_| None), Ok(6));
+        // The inverse of 4 modulo 7 is 2, so this is 10 * 2 * 3 modulo 7
+        assert_eq!(parse("10 / 4 * 3").evaluate_mod(|_| None, 7), Ok(4));
+        assert_eq!(
+            parse("10 / 2").evaluate_mod(|_| None, 8),
+            Err(ExpressionError::NotInvertible {
+                value: 2,
+                modulus: 8
+            })
+        );
+        assert_eq!(
+            parse("1").evaluate_mod(|_| None, 0),
+            Err(ExpressionError::InvalidModulus(0))
+        );
+        assert_eq!(
+            parse("1").evaluate_mod(|_| None, -5),
+            Err(ExpressionError::InvalidModulus(-5))
+        );
+        assert_eq!(
+            parse("1 / (a - a)").evaluate(|_| Some(3)),
+            Err(ExpressionError::DivisionByZero)
+        );
+        assert_eq!(
+            parse("new + 1").evaluate(old),
+            Err(ExpressionError::UnknownVariable("new".to_string()))
+        );
+        assert_eq!(parse("humn - (dvpt + humn)").variables(), ["humn", "dvpt"]);
+    }
+
+    #[test]
+    fn monkey_math() {
+        let monkeys = |name: &str| match name {
+            "dvpt" => Some(3),
+            "hmdt" => Some(32),
+            "zczc" => Some(2),
+            _ => None,
+        };
+        let left = parse("(4 + 2 * (humn - dvpt)) / 4");
+        let right = parse("(hmdt - zczc) * 5");
+
+        assert_eq!(
+            left.solve(&right, "humn", monkeys),
+            Ok(Rational::from(301i64))
+        );
+        assert_eq!(
+            parse("2 * x").solve(&Expr::Number(3), "x", |_| None),
+            Ok(Rational::new(3, 2))
+        );
+        assert_eq!(
+            parse("x * x").solve(&Expr::Number(4), "x", |_| None),
+            Err(ExpressionError::NotLinear("x".to_string()))
+        );
+        assert_eq!(
+            parse("x - x").solve(&Expr::Number(4), "x", |_| None),
+            Err(ExpressionError::NoSolution)
+        );
+        assert_eq!(
+            parse("x - x").solve(&Expr::Number(0), "x", |_| None),
+            Err(ExpressionError::InfiniteSolutions)
+        );
+        assert_eq!(
+            parse("x * 9223372036854775807 * 9223372036854775807 * 9223372036854775807").solve(
+                &Expr::Number(1),
+                "x",
+                |_| None
+            ),
+            Err(ExpressionError::Overflow)
+        );
+    }
+
+    #[test]
+    fn bridge_repair() {
+        let equations = "190: 10 19
+3267: 81 40 27
+83: 17 5
+156: 15 6
+7290: 6 8 6 15
+161011: 16 10 13
+192: 17 8 14
+21037: 9 7 18 13
+292: 11 6 16 20";
+
+        let calibration = |operators: &[Operator]| {
+            let mut total = 0;
+
+            for line in equations.lines() {
+                let (target, operands) = line.split_once(": ").unwrap();
+                let target = target.parse().unwrap();
+                let operands = operands
+                    .split(' ')
+                    .map(|operand| operand.parse().unwrap())
+                    .collect::<Vec<u64>>();
+
+                if let Some(found) = find_operators(target, &operands, operators) {
+                    let value = operands[1..]
+                        .iter()
+                        .zip(&found)
+                        .try_fold(operands[0], |value, (&operand, operator)| {
+                            operator.apply(value, operand)
+                        });
+                    assert_eq!(value, Some(target));
+                    total += target;
+                }
+            }
+
+            total
+        };
+
+        assert_eq!(calibration(&[Operator::Add, Operator::Multiply]), 3749);
+        assert_eq!(
+            calibration(&[Operator::Add, Operator::Multiply, Operator::Concatenate]),
+            11387
+        );
+        assert_eq!(
+            find_operators(0, &[5, 3, 0], &[Operator::Add, Operator::Multiply]),
+            Some(vec![Operator::Add, Operator::Multiply])
+        );
+        assert_eq!(find_operators(5, &[5], &[Operator::Add]), Some(vec![]));
+        assert_eq!(find_operators(5, &[], &[Operator::Add]), None);
+        assert_eq!(Operator::Concatenate.apply(12, 345), Some(12345));
+        assert_eq!(Operator::Concatenate.apply(u64::MAX, 1), None);
+    }
+}
@@ -0,0 +1,140 @@
+use std::path::{Path, PathBuf};
+
+use thiserror::Error;
+
+use crate::{
+    config::{config_value, parse_crate_name, DEFAULT_CRATE_NAME},
+    encryption::{cache_key_path, encrypted_path, CacheKey, DecryptionError, CACHE_KEY_VAR},
+};
+
+/// Default directory, relative to the workspace root, in which `aoc-manager`
+/// caches the problem inputs. It can be changed with the `paths.cache` key of
+/// the configuration.
+pub const DEFAULT_CACHE_PATH: &str = ".input-cache";
+
+/// Environment variable that, when set, makes [`load_input`] run
+/// `aoc-manager fetch` whenever the input is missing from the cache.
+pub const AUTO_FETCH_VAR: &str = "AOC_AUTO_FETCH";
+
+/// Environment variable that, when set, makes [`load_input`] read the input
+/// from the file it points to instead of the input cache. `aoc-manager check`
+/// uses it to run a solution against the inputs of other accounts.
+pub const INPUT_VAR: &str = "AOC_INPUT";
+
+#[derive(Debug, Error)]
+pub enum InputError {
+    #[error("\"{}\" is not a valid AoC crate name.", .0)]
+    InvalidCrateName(String),
+    #[error(
+        "input not found at {}. run `cargo run -- fetch` from the workspace root, or set {}=1 to fetch it automatically.",
+        .0.display(),
+        AUTO_FETCH_VAR
+    )]
+    Missing(PathBuf),
+    #[error("automatic fetch of the input failed (see aoc-manager output above)")]
+    FetchFailed,
+    #[error(
+        "the input is only cached encrypted, and no key was found in {} or in the {} environment variable.",
+        .0.display(),
+        CACHE_KEY_VAR
+    )]
+    MissingKey(PathBuf),
+    #[error("the encrypted input at {} can't be decrypted", .path.display())]
+    Decryption {
+        path: PathBuf,
+        #[source]
+        source: DecryptionError,
+    },
+    #[error("the decrypted input at {} isn't valid UTF-8", .0.display())]
+    InvalidUtf8(PathBuf),
+    #[error("i/o error")]
+    Io(#[from] std::io::Error),
+}
+
+/// Returns the path of the cached input for the crate named `crate_name`, in
+/// the workspace containing `manifest_dir`. The cache directory and the
+/// pattern of the crate names are read from the configuration.
+pub fn input_path(crate_name: &str, manifest_dir: &str) -> Result<PathBuf, InputError> {
+    let root = workspace_root(manifest_dir);
+    let pattern =
+        config_value(root, &["naming", "crate"]).unwrap_or_else(|| DEFAULT_CRATE_NAME.to_string());
+    let cache =
+        config_value(root, &["paths", "cache"]).unwrap_or_else(|| DEFAULT_CACHE_PATH.to_string());
+
+    let (year, day) = parse_crate_name(&pattern, crate_name)
+        .ok_or_else(|| InputError::InvalidCrateName(crate_name.to_string()))?;
+
+    Ok(root.join(cache).join(format!("{year}_{day:02}.txt")))
+}
+
+fn workspace_root(manifest_dir: &str) -> &Path {
+    let manifest_dir = Path::new(manifest_dir);
+    manifest_dir.parent().unwrap_or(manifest_dir)
+}
+
+fn auto_fetch(manifest_dir: &str) -> Result<(), InputError> {
+    let cargo = std::env::var_os("CARGO").unwrap_or_else(|| "cargo".into());
+    let status = std::process::Command::new(cargo)
+        .args(["run", "--quiet", "--bin", "aoc-manager", "--", "fetch"])
+        .current_dir(workspace_root(manifest_dir))
+        .status()?;
+
+    if status.success() {
+        Ok(())
+    } else {
+        Err(InputError::FetchFailed)
+    }
+}
+
+fn decrypt_input(path: &Path, manifest_dir: &str) -> Result<String, InputError> {
+    let key_path = cache_key_path(workspace_root(manifest_dir));
+    let key = CacheKey::load(&key_path)?.ok_or(InputError::MissingKey(key_path))?;
+    let plaintext =
+        key.decrypt(&std::fs::read(path)?)
+            .map_err(|source| InputError::Decryption {
+                path: path.to_path_buf(),
+                source,
+            })?;
+
+    String::from_utf8(plaintext).map_err(|_| InputError::InvalidUtf8(path.to_path_buf()))
+}
+
+/// Reads the input of a day crate from the workspace's input cache at
+/// runtime, or from the file given by the [`INPUT_VAR`] environment variable.
+/// If only the encrypted copy of the input is cached, it is decrypted with the
+/// key of the cache. If the input is missing and the [`AUTO_FETCH_VAR`]
+/// environment variable is set, `aoc-manager fetch` is run first.
+///
+/// This is usually called through the [`input!`](crate::input) macro.
+pub fn load_input(crate_name: &str, manifest_dir: &str) -> Result<String, InputError> {
+    if let Some(path) = std::env::var_os(INPUT_VAR) {
+        return Ok(std::fs::read_to_string(path)?);
+    }
+
+    let path = input_path(crate_name, manifest_dir)?;
+    let encrypted = encrypted_path(&path);
+
+    if !path.is_file() && encrypted.is_file() {
+        return decrypt_input(&encrypted, manifest_dir);
+    }
+
+    if !path.is_file() && std::env::var_os(AUTO_FETCH_VAR).is_some() {
+        auto_fetch(manifest_dir)?;
+    }
+
+    if path.is_file() {
+        Ok(std::fs::read_to_string(path)?)
+    } else {
+        Err(InputError::Missing(path))
+    }
+}
+
+/// Loads the input of the calling day crate from the input cache, and panics
+/// with an explanation if it isn't available.
+#[macro_export]
+macro_rules! input {
+    () => {
+        $crate::load_input(env!("CARGO_PKG_NAME"), env!("CARGO_MANIFEST_DIR"))
+            .unwrap_or_else(|e| panic!("{e}"))
+    };
+}
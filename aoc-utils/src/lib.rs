@@ -1,5 +1,35 @@
 use std::str::FromStr;
 
+mod compression;
+mod config;
+mod counter;
+mod encryption;
+mod expression;
+mod input;
+mod memo;
+mod order;
+mod region;
+mod runner;
+mod sequence;
+mod sparse_grid;
+mod union_find;
+mod vm;
+
+pub use compression::*;
+pub use config::*;
+pub use counter::*;
+pub use encryption::*;
+pub use expression::*;
+pub use input::*;
+pub use memo::*;
+pub use order::*;
+pub use region::*;
+pub use runner::*;
+pub use sequence::*;
+pub use sparse_grid::*;
+pub use union_find::*;
+pub use vm::*;
+
 pub fn iter_input_newline_separated(input: &str) -> impl Iterator<Item = &str> {
     input.split("\n\n")
 }
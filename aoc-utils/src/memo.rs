@@ -0,0 +1,311 @@
+use std::{
+    borrow::Borrow,
+    collections::{HashMap, VecDeque},
+    fmt,
+    hash::Hash,
+};
+
+/// Counters of a [`Memo`], to check that the cache is actually useful.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct MemoStats {
+    pub hits: usize,
+    pub misses: usize,
+    pub evictions: usize,
+}
+
+impl MemoStats {
+    /// Returns the share of lookups that were found in the cache, between 0
+    /// and 1.
+    pub fn hit_rate(&self) -> f64 {
+        match self.hits + self.misses {
+            0 => 0.0,
+            lookups => self.hits as f64 / lookups as f64,
+        }
+    }
+}
+
+impl fmt::Display for MemoStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} hits, {} misses ({:.1}% hit rate), {} evictions",
+            self.hits,
+            self.misses,
+            self.hit_rate() * 100.0,
+            self.evictions
+        )
+    }
+}
+
+/// A cache of the results of a function by its arguments, usually for a
+/// recursive function. Keys can borrow from the input, such as tuples of
+/// slices of it, as long as the memo doesn't outlive it.
+///
+/// A bounded memo evicts its oldest entries once full.
+#[derive(Debug, Clone)]
+pub struct Memo<K, V> {
+    values: HashMap<K, V>,
+    /// Keys by order of insertion, only kept when the memo is bounded.
+    order: VecDeque<K>,
+    capacity: Option<usize>,
+    stats: MemoStats,
+}
+
+impl<K, V> Default for Memo<K, V> {
+    fn default() -> Self {
+        Self {
+            values: HashMap::new(),
+            order: VecDeque::new(),
+            capacity: None,
+            stats: MemoStats::default(),
+        }
+    }
+}
+
+impl<K: Hash + Eq + Clone, V: Clone> Memo<K, V> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a memo that keeps at most `capacity` values.
+    pub fn bounded(capacity: usize) -> Self {
+        Self {
+            capacity: Some(capacity),
+            ..Self::default()
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    pub fn stats(&self) -> MemoStats {
+        self.stats
+    }
+
+    /// Removes every value, but keeps the statistics.
+    pub fn clear(&mut self) {
+        self.values.clear();
+        self.order.clear();
+    }
+
+    pub fn reset_stats(&mut self) {
+        self.stats = MemoStats::default();
+    }
+
+    /// Returns the cached value of a key, counting a hit or a miss.
+    pub fn get<Q>(&mut self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let value = self.values.get(key).cloned();
+        if value.is_some() {
+            self.stats.hits += 1;
+        } else {
+            self.stats.misses += 1;
+        }
+
+        value
+    }
+
+    pub fn insert(&mut self, key: K, value: V) {
+        if let Some(cached) = self.values.get_mut(&key) {
+            *cached = value;
+            return;
+        }
+
+        if let Some(capacity) = self.capacity {
+            if capacity == 0 {
+                return;
+            }
+
+            while self.values.len() >= capacity {
+                let Some(oldest) = self.order.pop_front() else {
+                    break;
+                };
+                self.values.remove(&oldest);
+                self.stats.evictions += 1;
+            }
+            self.order.push_back(key.clone());
+        }
+
+        self.values.insert(key, value);
+    }
+
+    /// Returns the cached value of a key, or computes and caches it. The
+    /// computation gets the memo back, so a recursive function can be written
+    /// as:
+    ///
+    /// ```
+    /// use aoc_utils::Memo;
+    ///
+    /// fn count(memo: &mut Memo<u64, u64>, n: u64) -> u64 {
+    ///     memo.get_or_insert_with(n, |memo, &n| match n {
+    ///         0 | 1 => 1,
+    ///         n => count(memo, n - 1) + count(memo, n - 2),
+    ///     })
+    /// }
+    ///
+    /// assert_eq!(count(&mut Memo::new(), 90), 4660046610375530309);
+    /// ```
+    pub fn get_or_insert_with(&mut self, key: K, compute: impl FnOnce(&mut Self, &K) -> V) -> V {
+        if let Some(value) = self.get(&key) {
+            return value;
+        }
+
+        let value = compute(self, &key);
+        self.insert(key, value.clone());
+
+        value
+    }
+
+    /// Calls a recursive function through the memo. The function gets the
+    /// arguments and a callback for its recursive calls, which go through the
+    /// memo too, so it can be a closure:
+    ///
+    /// ```
+    /// use aoc_utils::Memo;
+    ///
+    /// let mut memo: Memo<u64, u64> = Memo::new();
+    /// let ways = memo.recurse(90, &|ways, n: u64| match n {
+    ///     0 | 1 => 1,
+    ///     n => ways(n - 1) + ways(n - 2),
+    /// });
+    ///
+    /// assert_eq!(ways, 4660046610375530309);
+    /// ```
+    pub fn recurse<F>(&mut self, key: K, function: &F) -> V
+    where
+        F: Fn(&mut dyn FnMut(K) -> V, K) -> V,
+    {
+        if let Some(value) = self.get(&key) {
+            return value;
+        }
+
+        let value = function(&mut |key| self.recurse(key, function), key.clone());
+        self.insert(key, value.clone());
+
+        value
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Counts the arrangements of damaged springs matching the groups.
+    fn arrangements<'a>(
+        memo: &mut Memo<(&'a [u8], &'a [usize]), u64>,
+        springs: &'a [u8],
+        groups: &'a [usize],
+    ) -> u64 {
+        memo.get_or_insert_with((springs, groups), |memo, &(springs, groups)| {
+            let Some((&group, rest)) = groups.split_first() else {
+                return u64::from(!springs.contains(&b'#'));
+            };
+            let Some(&first) = springs.first() else {
+                return 0;
+            };
+
+            let mut count = 0;
+            if first != b'#' {
+                count += arrangements(memo, &springs[1..], groups);
+            }
+            if springs.len() >= group
+                && !springs[..group].contains(&b'.')
+                && springs.get(group) != Some(&b'#')
+            {
+                let after = springs.len().min(group + 1);
+                count += arrangements(memo, &springs[after..], rest);
+            }
+
+            count
+        })
+    }
+
+    #[test]
+    fn hot_springs() {
+        let records = [
+            ("???.###", vec![1, 1, 3]),
+            (".??..??...?##.", vec![1, 1, 3]),
+            ("?#?#?#?#?#?#?#?", vec![1, 3, 1, 6]),
+            ("????.#...#...", vec![4, 1, 1]),
+            ("????.######..#####.", vec![1, 6, 5]),
+            ("?###????????", vec![3, 2, 1]),
+        ];
+        let unfolded = records
+            .iter()
+            .map(|(springs, groups)| ([*springs; 5].join("?"), groups.repeat(5)))
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            records
+                .iter()
+                .map(|(springs, groups)| arrangements(&mut Memo::new(), springs.as_bytes(), groups))
+                .sum::<u64>(),
+            21
+        );
+        assert_eq!(
+            unfolded
+                .iter()
+                .map(|(springs, groups)| arrangements(&mut Memo::new(), springs.as_bytes(), groups))
+                .sum::<u64>(),
+            525152
+        );
+    }
+
+    #[test]
+    fn stats() {
+        let mut memo = Memo::new();
+        let fibonacci = memo.recurse(30, &|fibonacci, n: u64| match n {
+            0 | 1 => n,
+            n => fibonacci(n - 1) + fibonacci(n - 2),
+        });
+
+        assert_eq!(fibonacci, 832040);
+        assert_eq!(memo.len(), 31);
+        assert_eq!(
+            memo.stats(),
+            MemoStats {
+                hits: 28,
+                misses: 31,
+                evictions: 0
+            }
+        );
+
+        memo.clear();
+        memo.reset_stats();
+        assert!(memo.is_empty());
+        assert_eq!(memo.get(&30), None);
+        assert_eq!(
+            memo.stats().to_string(),
+            "0 hits, 1 misses (0.0% hit rate), 0 evictions"
+        );
+    }
+
+    #[test]
+    fn bounded() {
+        let mut memo = Memo::bounded(2);
+        memo.insert("a", 1);
+        memo.insert("b", 2);
+        memo.insert("a", 3);
+        memo.insert("c", 4);
+
+        // The oldest entry goes first, even if it was updated since
+        assert_eq!(memo.len(), 2);
+        assert_eq!(memo.get("a"), None);
+        assert_eq!(memo.get("b"), Some(2));
+        assert_eq!(memo.get_or_insert_with("c", |_, _| 0), 4);
+        assert_eq!(memo.stats().evictions, 1);
+        assert_eq!(memo.stats().hit_rate(), 2.0 / 3.0);
+
+        let mut disabled = Memo::bounded(0);
+        disabled.insert(1, 1);
+        assert!(disabled.is_empty());
+    }
+}
@@ -0,0 +1,292 @@
+use std::{
+    cmp::Reverse,
+    collections::{BTreeMap, BTreeSet, BinaryHeap},
+    fmt,
+};
+
+/// A cycle in the rules of a [`DependencyGraph`], which makes them impossible
+/// to follow. Holds the nodes of the cycle in order, each one having to come
+/// before the next, and the last one before the first.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cycle<T>(pub Vec<T>);
+
+impl<T: fmt::Debug> fmt::Display for Cycle<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "the rules have a cycle: ")?;
+        for node in &self.0 {
+            write!(f, "{node:?} -> ")?;
+        }
+
+        match self.0.first() {
+            Some(first) => write!(f, "{first:?}"),
+            None => Ok(()),
+        }
+    }
+}
+
+impl<T: fmt::Debug> std::error::Error for Cycle<T> {}
+
+/// Ordering rules between nodes, each one stating that a node must come before
+/// another. Sorts are deterministic: when several nodes could come next, the
+/// smallest one does.
+#[derive(Debug, Clone)]
+pub struct DependencyGraph<T> {
+    successors: BTreeMap<T, BTreeSet<T>>,
+}
+
+impl<T> Default for DependencyGraph<T> {
+    fn default() -> Self {
+        Self {
+            successors: BTreeMap::new(),
+        }
+    }
+}
+
+impl<T: Ord + Clone> DependencyGraph<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn add_node(&mut self, node: T) {
+        self.successors.entry(node).or_default();
+    }
+
+    /// Adds the rule that `before` must come before `after`.
+    pub fn add_rule(&mut self, before: T, after: T) {
+        self.add_node(after.clone());
+        self.successors.entry(before).or_default().insert(after);
+    }
+
+    pub fn nodes(&self) -> impl Iterator<Item = &T> {
+        self.successors.keys()
+    }
+
+    /// Returns the nodes that must come after `node` according to a rule.
+    pub fn successors(&self, node: &T) -> impl Iterator<Item = &T> {
+        self.successors.get(node).into_iter().flatten()
+    }
+
+    /// Whether a rule states that `before` must come before `after`. Only the
+    /// rules themselves are considered, not their transitive consequences.
+    pub fn has_rule(&self, before: &T, after: &T) -> bool {
+        self.successors
+            .get(before)
+            .is_some_and(|successors| successors.contains(after))
+    }
+
+    /// Returns every node, ordered so that each one comes before all the nodes
+    /// that must come after it.
+    pub fn topological_sort(&self) -> Result<Vec<T>, Cycle<T>> {
+        self.sort_nodes(self.successors.keys().cloned().collect())
+    }
+
+    /// Returns the given nodes ordered by the rules between them. Rules that
+    /// involve other nodes are ignored, so the rules only have to be
+    /// consistent within the subset. Repeated nodes are only returned once.
+    pub fn sort_subset(&self, nodes: &[T]) -> Result<Vec<T>, Cycle<T>> {
+        self.sort_nodes(nodes.iter().cloned().collect())
+    }
+
+    /// Whether a sequence follows the rules between its nodes.
+    pub fn respects(&self, sequence: &[T]) -> bool {
+        self.first_violation(sequence).is_none()
+    }
+
+    /// Returns the positions `(i, j)`, with `i < j`, of the first pair of nodes
+    /// of a sequence that breaks a rule, as the node at `j` must come before
+    /// the one at `i`.
+    pub fn first_violation(&self, sequence: &[T]) -> Option<(usize, usize)> {
+        let mut positions = BTreeMap::new();
+
+        for (j, node) in sequence.iter().enumerate() {
+            let violation = self
+                .successors(node)
+                .filter_map(|successor| positions.get(successor).copied())
+                .min();
+
+            if let Some(i) = violation {
+                return Some((i, j));
+            }
+
+            positions.entry(node).or_insert(j);
+        }
+
+        None
+    }
+
+    /// Returns a cycle of the rules, if there is one.
+    pub fn find_cycle(&self) -> Option<Cycle<T>> {
+        self.find_cycle_in(&self.successors.keys().cloned().collect())
+    }
+
+    /// Sorts the nodes with Kahn's algorithm, smallest available node first.
+    fn sort_nodes(&self, nodes: BTreeSet<T>) -> Result<Vec<T>, Cycle<T>> {
+        let mut predecessors = nodes
+            .iter()
+            .map(|node| (node, 0usize))
+            .collect::<BTreeMap<_, _>>();
+
+        for node in &nodes {
+            for successor in self.successors(node) {
+                if let Some(count) = predecessors.get_mut(successor) {
+                    *count += 1;
+                }
+            }
+        }
+
+        let mut available = predecessors
+            .iter()
+            .filter(|(_, &count)| count == 0)
+            .map(|(&node, _)| Reverse(node))
+            .collect::<BinaryHeap<_>>();
+        let mut sorted = Vec::with_capacity(nodes.len());
+
+        while let Some(Reverse(node)) = available.pop() {
+            sorted.push(node.clone());
+
+            for successor in self.successors(node) {
+                if let Some(count) = predecessors.get_mut(successor) {
+                    *count -= 1;
+                    if *count == 0 {
+                        available.push(Reverse(successor));
+                    }
+                }
+            }
+        }
+
+        if sorted.len() == nodes.len() {
+            Ok(sorted)
+        } else {
+            // The nodes that were never available are on a cycle, or after one
+            let remaining = predecessors
+                .into_iter()
+                .filter(|(_, count)| *count > 0)
+                .map(|(node, _)| node.clone())
+                .collect();
+
+            Err(self
+                .find_cycle_in(&remaining)
+                .expect("nodes left by Kahn's algorithm should contain a cycle"))
+        }
+    }
+
+    /// Finds a cycle among the given nodes, with an iterative depth-first
+    /// search.
+    fn find_cycle_in(&self, nodes: &BTreeSet<T>) -> Option<Cycle<T>> {
+        let mut visited = BTreeSet::new();
+
+        for start in nodes {
+            if visited.contains(start) {
+                continue;
+            }
+
+            // The path from the start, with the successors left to explore
+            let mut path = vec![(start, self.successors(start))];
+            let mut on_path = BTreeSet::from([start]);
+            visited.insert(start);
+
+            while let Some((_, successors)) = path.last_mut() {
+                match successors.find(|successor| nodes.contains(*successor)) {
+                    Some(next) if on_path.contains(next) => {
+                        let position = path.iter().position(|(node, _)| *node == next)?;
+
+                        return Some(Cycle(
+                            path[position..]
+                                .iter()
+                                .map(|(node, _)| (*node).clone())
+                                .collect(),
+                        ));
+                    }
+                    Some(next) => {
+                        if visited.insert(next) {
+                            on_path.insert(next);
+                            path.push((next, self.successors(next)));
+                        }
+                    }
+                    None => {
+                        if let Some((node, _)) = path.pop() {
+                            on_path.remove(node);
+                        }
+                    }
+                }
+            }
+        }
+
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RULES: &str = "47|53 97|13 97|61 97|47 75|29 61|13 75|53 29|13 97|29 53|29 61|53 97|53 \
+                         61|29 47|13 75|47 97|75 47|61 75|61 47|29 75|13 53|13";
+
+    fn page_rules() -> DependencyGraph<u32> {
+        let mut graph = DependencyGraph::new();
+        for rule in RULES.split_whitespace() {
+            let (before, after) = rule.split_once('|').unwrap();
+            graph.add_rule(before.parse().unwrap(), after.parse().unwrap());
+        }
+
+        graph
+    }
+
+    #[test]
+    fn print_queue() {
+        let graph = page_rules();
+        let updates: [&[u32]; 6] = [
+            &[75, 47, 61, 53, 29],
+            &[97, 61, 53, 29, 13],
+            &[75, 29, 13],
+            &[75, 97, 47, 61, 53],
+            &[61, 13, 29],
+            &[97, 13, 75, 29, 47],
+        ];
+        let (mut correct, mut fixed) = (0, 0);
+
+        for update in updates {
+            if graph.respects(update) {
+                correct += update[update.len() / 2];
+            } else {
+                let sorted = graph.sort_subset(update).unwrap();
+                assert!(graph.respects(&sorted));
+                fixed += sorted[sorted.len() / 2];
+            }
+        }
+
+        assert_eq!((correct, fixed), (143, 123));
+        assert_eq!(graph.first_violation(&[75, 97, 47, 61, 53]), Some((0, 1)));
+        assert_eq!(
+            graph.topological_sort().unwrap(),
+            [97, 75, 47, 61, 53, 29, 13]
+        );
+    }
+
+    #[test]
+    fn cycles() {
+        let mut graph = DependencyGraph::new();
+        graph.add_rule('a', 'b');
+        graph.add_rule('b', 'c');
+        graph.add_rule('c', 'd');
+        graph.add_node('e');
+        assert_eq!(graph.topological_sort(), Ok(vec!['a', 'b', 'c', 'd', 'e']));
+        assert_eq!(graph.find_cycle(), None);
+
+        graph.add_rule('d', 'b');
+        assert_eq!(graph.topological_sort(), Err(Cycle(vec!['b', 'c', 'd'])));
+        assert_eq!(graph.find_cycle(), Some(Cycle(vec!['b', 'c', 'd'])));
+        assert_eq!(
+            Cycle(vec!['b', 'c', 'd']).to_string(),
+            "the rules have a cycle: 'b' -> 'c' -> 'd' -> 'b'"
+        );
+
+        // Only the rules within the subset matter, and repeated nodes are
+        // dropped
+        assert_eq!(
+            graph.sort_subset(&['d', 'a', 'c', 'a']),
+            Ok(vec!['a', 'c', 'd'])
+        );
+    }
+}
@@ -0,0 +1,277 @@
+/// Offsets of the four neighbours of a cell, clockwise from the top.
+const NEIGHBOURS: [(isize, isize); 4] = [(0, -1), (1, 0), (0, 1), (-1, 0)];
+
+/// The smallest rectangle containing a region, as inclusive coordinates.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Bounds {
+    pub min: (usize, usize),
+    pub max: (usize, usize),
+}
+
+impl Bounds {
+    pub fn width(&self) -> usize {
+        self.max.0 - self.min.0 + 1
+    }
+
+    pub fn height(&self) -> usize {
+        self.max.1 - self.min.1 + 1
+    }
+}
+
+/// A connected region of a grid, whose cells are `(x, y)` coordinates.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Region {
+    pub label: usize,
+    pub cells: Vec<(usize, usize)>,
+    /// Number of cell edges between the region and the rest of the grid.
+    pub perimeter: usize,
+    /// Number of corners of the outline of the region, holes included, which
+    /// is also its number of straight sides.
+    pub corners: usize,
+    pub bounds: Bounds,
+    /// Number of areas enclosed by the region, whatever they contain. The
+    /// cells outside of the region are connected diagonally too, so that
+    /// cells touching the outside by a corner aren't enclosed: in `AAA/ABA/AAB`,
+    /// the region of `A` has no hole.
+    pub holes: usize,
+}
+
+impl Region {
+    pub fn area(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn sides(&self) -> usize {
+        self.corners
+    }
+}
+
+/// The connected regions of a grid, given as rows of cells. Two neighbouring
+/// cells (horizontally or vertically) belong to the same region if a predicate
+/// holds for them, such as their equality.
+///
+/// Regions are found with an iterative flood fill, so large regions don't
+/// overflow the stack. A [`UnionFind`](crate::UnionFind) of the cells would
+/// find the same regions, but the flood fill labels them in reading order and
+/// gathers their cells in the same pass, without a second pass to number the
+/// components.
+#[derive(Debug, Clone)]
+pub struct Regions {
+    labels: Vec<Vec<usize>>,
+    regions: Vec<Region>,
+}
+
+impl Regions {
+    /// Finds the regions of cells connected by `connected`, which is called on
+    /// pairs of neighbouring cells.
+    pub fn new<T>(grid: &[Vec<T>], connected: impl Fn(&T, &T) -> bool) -> Self {
+        let mut labels = grid
+            .iter()
+            .map(|row| vec![usize::MAX; row.len()])
+            .collect::<Vec<_>>();
+        let mut regions = Vec::new();
+
+        for y in 0..grid.len() {
+            for x in 0..grid[y].len() {
+                if labels[y][x] != usize::MAX {
+                    continue;
+                }
+
+                let label = regions.len();
+                let mut cells = vec![(x, y)];
+                let mut stack = vec![(x, y)];
+                labels[y][x] = label;
+
+                while let Some((x, y)) = stack.pop() {
+                    for (nx, ny) in neighbours(grid, x, y) {
+                        if labels[ny][nx] == usize::MAX && connected(&grid[y][x], &grid[ny][nx]) {
+                            labels[ny][nx] = label;
+                            cells.push((nx, ny));
+                            stack.push((nx, ny));
+                        }
+                    }
+                }
+
+                cells.sort_by_key(|&(x, y)| (y, x));
+                regions.push(measure(&labels, label, cells));
+            }
+        }
+
+        Self { labels, regions }
+    }
+
+    /// Finds the regions of equal cells.
+    pub fn of_equal<T: PartialEq>(grid: &[Vec<T>]) -> Self {
+        Self::new(grid, T::eq)
+    }
+
+    /// Returns the label of the region of a cell, which is its index in
+    /// [`Regions::regions`].
+    pub fn label(&self, x: usize, y: usize) -> usize {
+        self.labels[y][x]
+    }
+
+    pub fn region_at(&self, x: usize, y: usize) -> &Region {
+        &self.regions[self.label(x, y)]
+    }
+
+    /// Returns the regions, by order of their first cell in reading order.
+    pub fn regions(&self) -> &[Region] {
+        &self.regions
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Region> {
+        self.regions.iter()
+    }
+
+    pub fn len(&self) -> usize {
+        self.regions.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.regions.is_empty()
+    }
+}
+
+fn neighbours<T>(grid: &[Vec<T>], x: usize, y: usize) -> impl Iterator<Item = (usize, usize)> + '_ {
+    NEIGHBOURS.into_iter().filter_map(move |(dx, dy)| {
+        let (nx, ny) = (x.checked_add_signed(dx)?, y.checked_add_signed(dy)?);
+
+        (nx < grid.get(ny)?.len()).then_some((nx, ny))
+    })
+}
+
+fn measure(labels: &[Vec<usize>], label: usize, cells: Vec<(usize, usize)>) -> Region {
+    let inside = |x: isize, y: isize| {
+        usize::try_from(y)
+            .ok()
+            .and_then(|y| labels.get(y))
+            .zip(usize::try_from(x).ok())
+            .and_then(|(row, x)| row.get(x))
+            == Some(&label)
+    };
+
+    let mut perimeter = 0;
+    let mut corners = 0;
+    let mut edges = 0;
+    let mut squares = 0;
+    let mut bounds = Bounds {
+        min: cells[0],
+        max: cells[0],
+    };
+
+    for &(x, y) in &cells {
+        bounds.min = (bounds.min.0.min(x), bounds.min.1.min(y));
+        bounds.max = (bounds.max.0.max(x), bounds.max.1.max(y));
+        let (x, y) = (x as isize, y as isize);
+
+        perimeter += NEIGHBOURS
+            .iter()
+            .filter(|(dx, dy)| !inside(x + dx, y + dy))
+            .count();
+
+        // A corner is either convex (both sides outside), or concave (both
+        // sides inside, but not the diagonal)
+        for (dx, dy) in [(1, 1), (1, -1), (-1, 1), (-1, -1)] {
+            let (a, b, c) = (inside(x + dx, y), inside(x, y + dy), inside(x + dx, y + dy));
+
+            if (!a && !b) || (a && b && !c) {
+                corners += 1;
+            }
+        }
+
+        // Each edge and 2x2 square is counted from its top left cell
+        edges += usize::from(inside(x + 1, y)) + usize::from(inside(x, y + 1));
+        squares += usize::from(inside(x + 1, y) && inside(x, y + 1) && inside(x + 1, y + 1));
+    }
+
+    // The Euler characteristic of a connected region, V - E + F, is 1 minus
+    // its number of holes
+    let holes = 1 + edges - cells.len() - squares;
+
+    Region {
+        label,
+        cells,
+        perimeter,
+        corners,
+        bounds,
+        holes,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn grid(text: &str) -> Vec<Vec<char>> {
+        text.lines().map(|line| line.chars().collect()).collect()
+    }
+
+    #[test]
+    fn fence_prices() {
+        let regions = Regions::of_equal(&grid(
+            "RRRRIICCFF
+RRRRIICCCF
+VVRRRCCFFF
+VVRCCCJFFF
+VVVVCJJCFE
+VVIVCCJJEE
+VVIIICJJEE
+MIIIIIJJEE
+MIIISIJEEE
+MMMISSJEEE",
+        ));
+
+        assert_eq!(regions.len(), 11);
+        assert_eq!(
+            regions
+                .iter()
+                .map(|r| r.area() * r.perimeter)
+                .sum::<usize>(),
+            1930
+        );
+        assert_eq!(
+            regions.iter().map(|r| r.area() * r.sides()).sum::<usize>(),
+            1206
+        );
+
+        let r = regions.region_at(0, 0);
+        assert_eq!((r.label, r.area(), r.perimeter, r.sides()), (0, 12, 18, 10));
+        assert_eq!(
+            r.bounds,
+            Bounds {
+                min: (0, 0),
+                max: (4, 3)
+            }
+        );
+    }
+
+    #[test]
+    fn ring() {
+        let regions = Regions::of_equal(&grid("AAA\nABA\nAAA"));
+        let (ring, inside) = (regions.region_at(0, 0), regions.region_at(1, 1));
+
+        assert_eq!(regions.len(), 2);
+        assert_eq!((ring.area(), ring.perimeter, ring.sides()), (8, 16, 8));
+        assert_eq!(ring.holes, 1);
+        assert_eq!(inside.holes, 0);
+        assert_eq!((inside.bounds.width(), inside.bounds.height()), (1, 1));
+    }
+
+    #[test]
+    fn diagonal_gaps_are_not_holes() {
+        let regions = Regions::of_equal(&grid("AAA\nABA\nAAB"));
+
+        assert_eq!(regions.len(), 3);
+        assert_eq!(regions.region_at(0, 0).holes, 0);
+    }
+
+    #[test]
+    fn custom_connection() {
+        let regions = Regions::new(&grid("ab\nCd"), |a, b| a.is_lowercase() == b.is_lowercase());
+
+        assert_eq!(regions.len(), 2);
+        assert_eq!(regions.label(1, 1), regions.label(0, 0));
+        assert_eq!(regions.region_at(0, 1).area(), 1);
+    }
+}
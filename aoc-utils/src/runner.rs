@@ -0,0 +1,69 @@
+/// The value returned by a part of a solution. A part can either return its
+/// answer, or print it itself and return `()`.
+pub trait Answer {
+    fn into_answer(self) -> Option<String>;
+}
+
+impl Answer for () {
+    fn into_answer(self) -> Option<String> {
+        None
+    }
+}
+
+impl<T: Answer> Answer for Option<T> {
+    fn into_answer(self) -> Option<String> {
+        self.and_then(Answer::into_answer)
+    }
+}
+
+macro_rules! impl_answer {
+    ($($t:ty),*) => {
+        $(
+            impl Answer for $t {
+                fn into_answer(self) -> Option<String> {
+                    Some(self.to_string())
+                }
+            }
+        )*
+    };
+}
+
+impl_answer!(
+    u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize, char, String, &str
+);
+
+/// Runs a part of a solution, and prints its answer on a line of the form
+/// `Part 1: <answer>`, which is what `aoc-manager` reads.
+pub fn run_part<A: Answer>(part: u8, solve: impl FnOnce() -> A) {
+    if let Some(answer) = solve().into_answer() {
+        println!("Part {part}: {answer}");
+    }
+}
+
+/// Defines the `main` function of a day crate, which loads its input with
+/// [`input!`](crate::input) and runs the given parts on it, in order. Each
+/// part takes the input as a `&str` and returns its [`Answer`].
+///
+/// ```ignore
+/// fn part_1(input: &str) -> usize {
+///     input.lines().count()
+/// }
+///
+/// aoc_utils::main!(part_1);
+/// ```
+#[macro_export]
+macro_rules! main {
+    ($part_1:path $(,)?) => {
+        fn main() {
+            let input = $crate::input!();
+            $crate::run_part(1, || $part_1(&input));
+        }
+    };
+    ($part_1:path, $part_2:path $(,)?) => {
+        fn main() {
+            let input = $crate::input!();
+            $crate::run_part(1, || $part_1(&input));
+            $crate::run_part(2, || $part_2(&input));
+        }
+    };
+}
@@ -0,0 +1,509 @@
+use std::{
+    fmt,
+    ops::{Add, Div, Mul, Neg, Sub},
+};
+
+/// Returns the successive differences of a sequence: the sequence itself, then
+/// the differences between its consecutive values, and so on until a row is
+/// all zeros or has a single value.
+///
+/// # Panics
+///
+/// If a difference overflows an `i64`, which [`extrapolate`] avoids by
+/// computing them with 128 bits.
+pub fn differences(values: &[i64]) -> Vec<Vec<i64>> {
+    let mut rows = vec![values.to_vec()];
+
+    loop {
+        let last = &rows[rows.len() - 1];
+        if last.len() <= 1 || last.iter().all(|&value| value == 0) {
+            return rows;
+        }
+
+        let next = last
+            .windows(2)
+            .map(|pair| {
+                pair[1]
+                    .checked_sub(pair[0])
+                    .expect("the difference of two values should fit in an i64")
+            })
+            .collect();
+        rows.push(next);
+    }
+}
+
+/// Returns the value of a sequence at `index`, which can be past either of its
+/// ends, assuming it is a polynomial of its index: this is exact if its
+/// differences reach zero. Uses Newton's forward formula, so only the first
+/// value of each row of differences is needed.
+///
+/// Returns `None` for an empty sequence, or if the value or one of the
+/// intermediate results, computed with 128 bits, overflows.
+pub fn extrapolate(values: &[i64], index: i64) -> Option<i64> {
+    let mut row = values
+        .iter()
+        .map(|&value| value as i128)
+        .collect::<Vec<_>>();
+    let mut firsts = Vec::new();
+
+    loop {
+        firsts.push(*row.first()?);
+        if row.len() == 1 || row.iter().all(|&value| value == 0) {
+            break;
+        }
+
+        row = row
+            .windows(2)
+            .map(|pair| pair[1].checked_sub(pair[0]))
+            .collect::<Option<_>>()?;
+    }
+
+    // The binomial coefficient C(index, k), which is an integer even for a
+    // negative index
+    let mut binomial = 1i128;
+    let mut value = 0i128;
+
+    for (k, first) in firsts.into_iter().enumerate() {
+        if k > 0 {
+            binomial = binomial.checked_mul(index as i128 - k as i128 + 1)? / k as i128;
+        }
+        value = value.checked_add(binomial.checked_mul(first)?)?;
+    }
+
+    i64::try_from(value).ok()
+}
+
+/// Returns the value following a sequence.
+pub fn extrapolate_forward(values: &[i64]) -> Option<i64> {
+    extrapolate(values, values.len() as i64)
+}
+
+/// Returns the value preceding a sequence.
+pub fn extrapolate_backward(values: &[i64]) -> Option<i64> {
+    extrapolate(values, -1)
+}
+
+/// Returns the value at step `step` of a quantity that grows polynomially
+/// every `period` steps, from its values sampled at the steps `start`,
+/// `start + period`, `start + 2 * period`, and so on. Returns `None` if `step`
+/// isn't one of those steps, if there are no samples, or on overflow.
+///
+/// Needs one more sample than the degree of the polynomial, such as three for
+/// quadratic growth.
+pub fn extrapolate_steps(start: i64, period: i64, samples: &[i64], step: i64) -> Option<i64> {
+    let offset = step.checked_sub(start)?;
+    if period == 0 || offset.checked_rem(period)? != 0 {
+        return None;
+    }
+
+    extrapolate(samples, offset / period)
+}
+
+/// Returns the value at `x` of the polynomial going through the given points,
+/// with Lagrange's formula. Returns `None` if two points share an `x`.
+pub fn lagrange<X, Y>(points: &[(X, Y)], x: impl Into<Rational>) -> Option<Rational>
+where
+    X: Into<Rational> + Copy,
+    Y: Into<Rational> + Copy,
+{
+    let x = x.into();
+    let points = points
+        .iter()
+        .map(|&(x, y)| (x.into(), y.into()))
+        .collect::<Vec<(Rational, Rational)>>();
+
+    let mut value = Rational::ZERO;
+    for (i, &(xi, yi)) in points.iter().enumerate() {
+        let mut term = yi;
+
+        for (j, &(xj, _)) in points.iter().enumerate() {
+            if i != j {
+                term = term * (x - xj) / xi.checked_sub_nonzero(xj)?;
+            }
+        }
+
+        value = value + term;
+    }
+
+    Some(value)
+}
+
+/// An exact fraction, always kept in lowest terms with a positive
+/// denominator.
+///
+/// Arithmetic panics if a numerator or a denominator overflows an `i128`,
+/// in release builds too. This is far beyond the values of a puzzle, but can
+/// be reached by interpolating dozens of points with large coordinates, or
+/// with arbitrary operands: the `checked_*` methods return `None` instead.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Rational {
+    numerator: i128,
+    denominator: i128,
+}
+
+impl Rational {
+    pub const ZERO: Self = Self {
+        numerator: 0,
+        denominator: 1,
+    };
+    pub const ONE: Self = Self {
+        numerator: 1,
+        denominator: 1,
+    };
+
+    /// Creates the fraction `numerator / denominator`.
+    ///
+    /// # Panics
+    ///
+    /// If the denominator is zero.
+    pub fn new(numerator: i128, denominator: i128) -> Self {
+        assert!(denominator != 0, "a fraction can't have a zero denominator");
+
+        let divisor = gcd(numerator, denominator) * denominator.signum();
+        Self {
+            numerator: numerator / divisor,
+            denominator: denominator / divisor,
+        }
+    }
+
+    pub fn numerator(&self) -> i128 {
+        self.numerator
+    }
+
+    pub fn denominator(&self) -> i128 {
+        self.denominator
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.numerator == 0
+    }
+
+    pub fn is_integer(&self) -> bool {
+        self.denominator == 1
+    }
+
+    /// Returns the fraction as an integer, if it is one.
+    pub fn to_integer(&self) -> Option<i128> {
+        self.is_integer().then_some(self.numerator)
+    }
+
+    /// Returns `self + other`, or `None` on overflow.
+    pub fn checked_add(self, other: Self) -> Option<Self> {
+        let numerator = self
+            .numerator
+            .checked_mul(other.denominator)?
+            .checked_add(other.numerator.checked_mul(self.denominator)?)?;
+
+        Some(Self::new(
+            numerator,
+            self.denominator.checked_mul(other.denominator)?,
+        ))
+    }
+
+    /// Returns `self - other`, or `None` on overflow.
+    pub fn checked_sub(self, other: Self) -> Option<Self> {
+        self.checked_add(other.checked_neg()?)
+    }
+
+    /// Returns `self * other`, or `None` on overflow.
+    pub fn checked_mul(self, other: Self) -> Option<Self> {
+        Some(Self::new(
+            self.numerator.checked_mul(other.numerator)?,
+            self.denominator.checked_mul(other.denominator)?,
+        ))
+    }
+
+    /// Returns `self / other`, or `None` if `other` is zero or on overflow.
+    pub fn checked_div(self, other: Self) -> Option<Self> {
+        if other.is_zero() {
+            return None;
+        }
+
+        Some(Self::new(
+            self.numerator.checked_mul(other.denominator)?,
+            self.denominator.checked_mul(other.numerator)?,
+        ))
+    }
+
+    /// Returns `-self`, or `None` on overflow.
+    pub fn checked_neg(self) -> Option<Self> {
+        Some(Self {
+            numerator: self.numerator.checked_neg()?,
+            denominator: self.denominator,
+        })
+    }
+
+    /// Returns `self - other`, or `None` if it is zero, to divide by it.
+    fn checked_sub_nonzero(self, other: Self) -> Option<Self> {
+        let difference = self - other;
+        (!difference.is_zero()).then_some(difference)
+    }
+}
+
+/// The panic message of the arithmetic operators of [`Rational`].
+const OVERFLOW: &str = "a fraction overflowed an i128";
+
+fn gcd(a: i128, b: i128) -> i128 {
+    let (mut a, mut b) = (a.abs(), b.abs());
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+
+    a.max(1)
+}
+
+impl From<i64> for Rational {
+    fn from(value: i64) -> Self {
+        Self::from(value as i128)
+    }
+}
+
+impl From<i128> for Rational {
+    fn from(value: i128) -> Self {
+        Self {
+            numerator: value,
+            denominator: 1,
+        }
+    }
+}
+
+impl fmt::Display for Rational {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_integer() {
+            write!(f, "{}", self.numerator)
+        } else {
+            write!(f, "{}/{}", self.numerator, self.denominator)
+        }
+    }
+}
+
+impl Add for Rational {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        self.checked_add(other).expect(OVERFLOW)
+    }
+}
+
+impl Sub for Rational {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        self.checked_sub(other).expect(OVERFLOW)
+    }
+}
+
+impl Mul for Rational {
+    type Output = Self;
+
+    fn mul(self, other: Self) -> Self {
+        self.checked_mul(other).expect(OVERFLOW)
+    }
+}
+
+impl Div for Rational {
+    type Output = Self;
+
+    /// # Panics
+    ///
+    /// If `other` is zero.
+    fn div(self, other: Self) -> Self {
+        assert!(!other.is_zero(), "a fraction can't be divided by zero");
+        self.checked_div(other).expect(OVERFLOW)
+    }
+}
+
+impl Neg for Rational {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        self.checked_neg().expect(OVERFLOW)
+    }
+}
+
+/// A polynomial with exact coefficients.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Polynomial {
+    /// The coefficients from the constant one up, without trailing zeros.
+    coefficients: Vec<Rational>,
+}
+
+impl Polynomial {
+    /// Creates a polynomial from its coefficients, from the constant one up.
+    pub fn new(coefficients: Vec<Rational>) -> Self {
+        let mut polynomial = Self { coefficients };
+        polynomial.trim();
+
+        polynomial
+    }
+
+    /// Returns the polynomial of lowest degree going through the given points,
+    /// with Newton's divided differences. Returns `None` if two points share
+    /// an `x`.
+    pub fn interpolate<X, Y>(points: &[(X, Y)]) -> Option<Self>
+    where
+        X: Into<Rational> + Copy,
+        Y: Into<Rational> + Copy,
+    {
+        let xs = points.iter().map(|&(x, _)| x.into()).collect::<Vec<_>>();
+        let mut divided = points.iter().map(|&(_, y)| y.into()).collect::<Vec<_>>();
+
+        // After step k, divided[i] is the divided difference of the points
+        // i - k to i, so divided[i] ends up as the i-th Newton coefficient
+        for k in 1..points.len() {
+            for i in (k..points.len()).rev() {
+                divided[i] =
+                    (divided[i] - divided[i - 1]) / xs[i].checked_sub_nonzero(xs[i - k])?;
+            }
+        }
+
+        // Expands c0 + (x - x0)(c1 + (x - x1)(c2 + ...)) from the inside out
+        let mut coefficients = Vec::new();
+        for (i, &coefficient) in divided.iter().enumerate().rev() {
+            // Multiplies by (x - xi), then adds the coefficient
+            let mut next = vec![Rational::ZERO; coefficients.len() + 1];
+            for (power, &c) in coefficients.iter().enumerate() {
+                next[power + 1] = next[power + 1] + c;
+                next[power] = next[power] - c * xs[i];
+            }
+            next[0] = next[0] + coefficient;
+            coefficients = next;
+        }
+
+        Some(Self::new(coefficients))
+    }
+
+    /// Returns the polynomial going through the values of a sequence, taken at
+    /// the indices `0, 1, 2...`.
+    pub fn fit(values: &[i64]) -> Self {
+        let points = values
+            .iter()
+            .enumerate()
+            .map(|(x, &y)| (x as i64, y))
+            .collect::<Vec<_>>();
+
+        Self::interpolate(&points).expect("the indices of a sequence are distinct")
+    }
+
+    pub fn coefficients(&self) -> &[Rational] {
+        &self.coefficients
+    }
+
+    /// Returns the degree of the polynomial, or `None` for the zero
+    /// polynomial.
+    pub fn degree(&self) -> Option<usize> {
+        self.coefficients.len().checked_sub(1)
+    }
+
+    pub fn evaluate(&self, x: impl Into<Rational>) -> Rational {
+        let x = x.into();
+
+        self.coefficients
+            .iter()
+            .rev()
+            .fold(Rational::ZERO, |value, &coefficient| {
+                value * x + coefficient
+            })
+    }
+
+    /// Evaluates the polynomial at an integer, if its value is an integer too.
+    pub fn evaluate_integer(&self, x: i64) -> Option<i64> {
+        self.evaluate(x)
+            .to_integer()
+            .and_then(|value| i64::try_from(value).ok())
+    }
+
+    fn trim(&mut self) {
+        while self.coefficients.last().is_some_and(Rational::is_zero) {
+            self.coefficients.pop();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HISTORIES: [[i64; 6]; 3] = [
+        [0, 3, 6, 9, 12, 15],
+        [1, 3, 6, 10, 15, 21],
+        [10, 13, 16, 21, 30, 45],
+    ];
+
+    #[test]
+    fn mirage_maintenance() {
+        let forward = HISTORIES.map(|values| extrapolate_forward(&values).unwrap());
+        let backward = HISTORIES.map(|values| extrapolate_backward(&values).unwrap());
+
+        assert_eq!(forward, [18, 28, 68]);
+        assert_eq!(backward, [-3, 0, 5]);
+        assert_eq!(forward.iter().sum::<i64>(), 114);
+        assert_eq!(backward.iter().sum::<i64>(), 2);
+
+        assert_eq!(
+            differences(&HISTORIES[2]),
+            [
+                vec![10, 13, 16, 21, 30, 45],
+                vec![3, 3, 5, 9, 15],
+                vec![0, 2, 4, 6],
+                vec![2, 2, 2],
+                vec![0, 0],
+            ]
+        );
+        assert_eq!(extrapolate(&HISTORIES[1], 100), Some(5151));
+        assert_eq!(
+            extrapolate_steps(65, 131, &[3, 9, 19], 65 + 131 * 4),
+            Some(51)
+        );
+        assert_eq!(extrapolate_steps(65, 131, &[3, 9, 19], 66), None);
+    }
+
+    #[test]
+    fn overflow() {
+        assert_eq!(extrapolate(&[], 0), None);
+        assert_eq!(extrapolate(&[i64::MAX, i64::MIN], 2), None);
+        assert_eq!(extrapolate(&[i64::MIN, i64::MAX], 1), Some(i64::MAX));
+        assert_eq!(extrapolate(&[0, 0, 1], i64::MAX), None);
+        assert_eq!(extrapolate_steps(i64::MIN, -1, &[0, 1], i64::MAX), None);
+    }
+
+    #[test]
+    fn interpolation() {
+        let points: [(i64, i64); 3] = [(0, 1), (1, 3), (2, 7)];
+        let half = Rational::new(1, 2);
+
+        assert_eq!(lagrange(&points, 3i64), Some(Rational::from(13i64)));
+        assert_eq!(lagrange(&points, half), Some(Rational::new(7, 4)));
+        assert_eq!(lagrange(&[(1i64, 1i64), (1, 2)], 0i64), None);
+
+        let polynomial = Polynomial::interpolate(&points).unwrap();
+        assert_eq!(polynomial.coefficients(), [Rational::ONE; 3]);
+        assert_eq!(polynomial.degree(), Some(2));
+        assert_eq!(polynomial.evaluate_integer(10), Some(111));
+        assert_eq!(Polynomial::fit(&[5, 5, 5]).degree(), Some(0));
+        assert_eq!(Polynomial::fit(&[0, 0]).degree(), None);
+    }
+
+    #[test]
+    fn rationals() {
+        assert_eq!(Rational::new(2, -4), Rational::new(-1, 2));
+        assert_eq!(Rational::new(2, -4).to_string(), "-1/2");
+        assert_eq!(Rational::new(6, 3).to_integer(), Some(2));
+        assert_eq!(
+            Rational::new(1, 3) + Rational::new(1, 6) - Rational::ONE,
+            Rational::new(-1, 2)
+        );
+        assert_eq!(
+            Rational::new(2, 3) * Rational::new(3, 4) / Rational::new(1, 2),
+            Rational::ONE
+        );
+        assert_eq!(Rational::from(i128::MAX).checked_add(Rational::ONE), None);
+        assert_eq!(Rational::ONE.checked_div(Rational::ZERO), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "overflowed")]
+    fn rational_overflow() {
+        let _ = Rational::from(i128::MAX) + Rational::ONE;
+    }
+}
@@ -0,0 +1,374 @@
+use std::{
+    collections::{hash_map, HashMap},
+    fmt,
+};
+
+/// Offsets of the four orthogonal neighbours of a cell, clockwise from the top.
+pub const ORTHOGONAL: [(i64, i64); 4] = [(0, -1), (1, 0), (0, 1), (-1, 0)];
+
+/// Offsets of the eight neighbours of a cell, diagonals included, clockwise
+/// from the top.
+pub const ADJACENT: [(i64, i64); 8] = [
+    (0, -1),
+    (1, -1),
+    (1, 0),
+    (1, 1),
+    (0, 1),
+    (-1, 1),
+    (-1, 0),
+    (-1, -1),
+];
+
+/// A rectangle of signed `(x, y)` coordinates, with inclusive bounds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Rect {
+    pub min: (i64, i64),
+    pub max: (i64, i64),
+}
+
+impl Rect {
+    /// Returns the rectangle made of a single point.
+    pub fn point(point: (i64, i64)) -> Self {
+        Self {
+            min: point,
+            max: point,
+        }
+    }
+
+    /// Returns the smallest rectangle containing the points, or `None` if
+    /// there are none.
+    pub fn around(points: impl IntoIterator<Item = (i64, i64)>) -> Option<Self> {
+        let mut points = points.into_iter();
+        let first = Self::point(points.next()?);
+
+        Some(points.fold(first, |bounds, point| bounds.including(point)))
+    }
+
+    pub fn width(&self) -> usize {
+        (self.max.0 - self.min.0 + 1) as usize
+    }
+
+    pub fn height(&self) -> usize {
+        (self.max.1 - self.min.1 + 1) as usize
+    }
+
+    pub fn area(&self) -> usize {
+        self.width() * self.height()
+    }
+
+    pub fn contains(&self, (x, y): (i64, i64)) -> bool {
+        (self.min.0..=self.max.0).contains(&x) && (self.min.1..=self.max.1).contains(&y)
+    }
+
+    /// Returns the smallest rectangle containing this one and a point.
+    pub fn including(&self, (x, y): (i64, i64)) -> Self {
+        Self {
+            min: (self.min.0.min(x), self.min.1.min(y)),
+            max: (self.max.0.max(x), self.max.1.max(y)),
+        }
+    }
+
+    /// Returns whether a point is on the edge of the rectangle.
+    fn on_edge(&self, (x, y): (i64, i64)) -> bool {
+        x == self.min.0 || x == self.max.0 || y == self.min.1 || y == self.max.1
+    }
+}
+
+/// A grid without fixed bounds, storing only its occupied cells by signed
+/// `(x, y)` coordinates, `y` going down. It keeps track of the smallest
+/// rectangle containing them.
+#[derive(Debug, Clone)]
+pub struct SparseGrid<T> {
+    cells: HashMap<(i64, i64), T>,
+    bounds: Option<Rect>,
+}
+
+impl<T> Default for SparseGrid<T> {
+    fn default() -> Self {
+        Self {
+            cells: HashMap::new(),
+            bounds: None,
+        }
+    }
+}
+
+impl<T: PartialEq> PartialEq for SparseGrid<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cells == other.cells
+    }
+}
+
+impl<T: Eq> Eq for SparseGrid<T> {}
+
+impl<T> SparseGrid<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a grid from the cells of a dense one, given as rows. The cell
+    /// at row `y` and column `x` is stored at `(x, y)` if `cell` returns a
+    /// value for it.
+    pub fn from_dense<U>(grid: &[Vec<U>], mut cell: impl FnMut(&U) -> Option<T>) -> Self {
+        let mut sparse = Self::new();
+
+        for (y, row) in grid.iter().enumerate() {
+            for (x, value) in row.iter().enumerate() {
+                if let Some(value) = cell(value) {
+                    sparse.insert((x as i64, y as i64), value);
+                }
+            }
+        }
+
+        sparse
+    }
+
+    /// Creates a grid from the characters of a text, as [`SparseGrid::from_dense`].
+    pub fn from_text(text: &str, mut cell: impl FnMut(char) -> Option<T>) -> Self {
+        let mut sparse = Self::new();
+
+        for (y, line) in text.lines().enumerate() {
+            for (x, c) in line.chars().enumerate() {
+                if let Some(value) = cell(c) {
+                    sparse.insert((x as i64, y as i64), value);
+                }
+            }
+        }
+
+        sparse
+    }
+
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    /// Returns the smallest rectangle containing every cell, or `None` if the
+    /// grid is empty.
+    pub fn bounds(&self) -> Option<Rect> {
+        self.bounds
+    }
+
+    /// Sets a cell, and returns its previous value.
+    pub fn insert(&mut self, position: (i64, i64), value: T) -> Option<T> {
+        self.bounds = Some(match self.bounds {
+            Some(bounds) => bounds.including(position),
+            None => Rect::point(position),
+        });
+
+        self.cells.insert(position, value)
+    }
+
+    /// Clears a cell, and returns its value. The bounds are computed again if
+    /// it was on their edge, which takes a time proportional to the number of
+    /// cells.
+    pub fn remove(&mut self, position: (i64, i64)) -> Option<T> {
+        let value = self.cells.remove(&position)?;
+
+        if self.bounds.is_some_and(|bounds| bounds.on_edge(position)) {
+            self.bounds = Rect::around(self.cells.keys().copied());
+        }
+
+        Some(value)
+    }
+
+    pub fn get(&self, position: (i64, i64)) -> Option<&T> {
+        self.cells.get(&position)
+    }
+
+    pub fn get_mut(&mut self, position: (i64, i64)) -> Option<&mut T> {
+        self.cells.get_mut(&position)
+    }
+
+    pub fn contains(&self, position: (i64, i64)) -> bool {
+        self.cells.contains_key(&position)
+    }
+
+    /// Returns the cells and their positions, in no particular order.
+    pub fn iter(&self) -> impl Iterator<Item = ((i64, i64), &T)> {
+        self.cells
+            .iter()
+            .map(|(&position, value)| (position, value))
+    }
+
+    pub fn positions(&self) -> impl Iterator<Item = (i64, i64)> + '_ {
+        self.cells.keys().copied()
+    }
+
+    /// Returns the occupied cells among the orthogonal neighbours of a
+    /// position.
+    pub fn neighbours(&self, position: (i64, i64)) -> impl Iterator<Item = ((i64, i64), &T)> {
+        self.occupied(position, &ORTHOGONAL)
+    }
+
+    /// Returns the occupied cells among the eight neighbours of a position,
+    /// diagonals included.
+    pub fn adjacent(&self, position: (i64, i64)) -> impl Iterator<Item = ((i64, i64), &T)> {
+        self.occupied(position, &ADJACENT)
+    }
+
+    fn occupied<'a>(
+        &'a self,
+        (x, y): (i64, i64),
+        offsets: &'static [(i64, i64)],
+    ) -> impl Iterator<Item = ((i64, i64), &'a T)> {
+        offsets.iter().filter_map(move |&(dx, dy)| {
+            let position = (x + dx, y + dy);
+            self.get(position).map(|value| (position, value))
+        })
+    }
+
+    /// Returns the cells within the bounds as rows, from the top left corner
+    /// of the bounds, with `empty` for the cells that aren't set.
+    pub fn to_dense(&self, empty: T) -> Vec<Vec<T>>
+    where
+        T: Clone,
+    {
+        let Some(bounds) = self.bounds else {
+            return Vec::new();
+        };
+
+        (bounds.min.1..=bounds.max.1)
+            .map(|y| {
+                (bounds.min.0..=bounds.max.0)
+                    .map(|x| self.get((x, y)).unwrap_or(&empty).clone())
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// Draws the cells within the bounds, one character per cell and one line
+    /// per row, for debugging.
+    pub fn render(&self, mut cell: impl FnMut(Option<&T>) -> char) -> String {
+        let Some(bounds) = self.bounds else {
+            return String::new();
+        };
+
+        let mut text = String::with_capacity((bounds.width() + 1) * bounds.height());
+        for y in bounds.min.1..=bounds.max.1 {
+            for x in bounds.min.0..=bounds.max.0 {
+                text.push(cell(self.get((x, y))));
+            }
+            text.push('\n');
+        }
+
+        text
+    }
+}
+
+impl SparseGrid<()> {
+    /// Creates a grid from a set of points.
+    pub fn from_points(points: impl IntoIterator<Item = (i64, i64)>) -> Self {
+        points.into_iter().map(|point| (point, ())).collect()
+    }
+}
+
+impl<T> FromIterator<((i64, i64), T)> for SparseGrid<T> {
+    fn from_iter<I: IntoIterator<Item = ((i64, i64), T)>>(cells: I) -> Self {
+        let mut grid = Self::new();
+        grid.extend(cells);
+
+        grid
+    }
+}
+
+impl<T> Extend<((i64, i64), T)> for SparseGrid<T> {
+    fn extend<I: IntoIterator<Item = ((i64, i64), T)>>(&mut self, cells: I) {
+        for (position, value) in cells {
+            self.insert(position, value);
+        }
+    }
+}
+
+impl<T> IntoIterator for SparseGrid<T> {
+    type Item = ((i64, i64), T);
+    type IntoIter = hash_map::IntoIter<(i64, i64), T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.cells.into_iter()
+    }
+}
+
+/// Draws the grid with `.` for the cells that aren't set.
+impl fmt::Display for SparseGrid<char> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.render(|cell| cell.copied().unwrap_or('.')))
+    }
+}
+
+/// Draws the grid with `#` for the points, and `.` elsewhere.
+impl fmt::Display for SparseGrid<()> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.render(|cell| if cell.is_some() { '#' } else { '.' }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn remove_shrinks_the_bounds() {
+        let mut grid = SparseGrid::from_points([(0, 0), (2, 1), (-3, 4), (1, 1)]);
+        assert_eq!(
+            grid.bounds(),
+            Some(Rect {
+                min: (-3, 0),
+                max: (2, 4)
+            })
+        );
+
+        // Inside the bounds, which don't change
+        assert_eq!(grid.remove((1, 1)), Some(()));
+        assert_eq!(grid.bounds().map(|bounds| bounds.area()), Some(30));
+
+        assert_eq!(grid.remove((-3, 4)), Some(()));
+        assert_eq!(
+            grid.bounds(),
+            Some(Rect {
+                min: (0, 0),
+                max: (2, 1)
+            })
+        );
+        assert_eq!(grid.remove((-3, 4)), None);
+
+        grid.remove((0, 0));
+        grid.remove((2, 1));
+        assert!(grid.is_empty());
+        assert_eq!(grid.bounds(), None);
+        assert_eq!(grid.to_string(), "");
+    }
+
+    #[test]
+    fn text_round_trip() {
+        let text = "..#\n#.#\n.##\n";
+        let grid = SparseGrid::from_text(text, |c| (c == '#').then_some(()));
+
+        assert_eq!(grid.len(), 5);
+        assert_eq!(grid.to_string(), text);
+        assert_eq!(grid.neighbours((1, 1)).count(), 3);
+        assert_eq!(grid.adjacent((1, 1)).count(), 5);
+    }
+
+    #[test]
+    fn negative_coordinates() {
+        let mut grid = SparseGrid::new();
+        grid.insert((-2, -1), 'S');
+        grid.insert((1, 0), 'B');
+        assert_eq!(grid.insert((1, 0), 'X'), Some('B'));
+
+        assert_eq!(grid.to_string(), "S...\n...X\n");
+        assert_eq!(
+            grid.to_dense('.'),
+            [vec!['S', '.', '.', '.'], vec!['.', '.', '.', 'X']]
+        );
+        assert_eq!(
+            grid.render(|cell| cell.map_or(' ', |_| '#')),
+            "#   \n   #\n"
+        );
+        assert_eq!(grid.get((-2, -1)), Some(&'S'));
+        assert!(!grid.contains((0, 0)));
+    }
+}
@@ -0,0 +1,382 @@
+use std::{collections::HashMap, hash::Hash};
+
+/// A disjoint-set forest over the indices `0..len`, with union by rank and path
+/// compression. Each set, or component, knows its size.
+#[derive(Debug, Clone, Default)]
+pub struct UnionFind {
+    parents: Vec<usize>,
+    ranks: Vec<u8>,
+    sizes: Vec<usize>,
+    components: usize,
+}
+
+impl UnionFind {
+    /// Creates `len` components, each with a single index.
+    pub fn new(len: usize) -> Self {
+        Self {
+            parents: (0..len).collect(),
+            ranks: vec![0; len],
+            sizes: vec![1; len],
+            components: len,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.parents.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.parents.is_empty()
+    }
+
+    /// Adds a new index in its own component, and returns it.
+    pub fn add(&mut self) -> usize {
+        let index = self.parents.len();
+        self.parents.push(index);
+        self.ranks.push(0);
+        self.sizes.push(1);
+        self.components += 1;
+
+        index
+    }
+
+    /// Returns the representative of the component of `index`, and makes the
+    /// indices on the way point directly to it.
+    pub fn find(&mut self, index: usize) -> usize {
+        let root = self.root(index);
+
+        let mut index = index;
+        while self.parents[index] != root {
+            index = std::mem::replace(&mut self.parents[index], root);
+        }
+
+        root
+    }
+
+    /// Returns the representative of the component of `index`, without
+    /// compressing the path to it.
+    pub fn root(&self, mut index: usize) -> usize {
+        while self.parents[index] != index {
+            index = self.parents[index];
+        }
+
+        index
+    }
+
+    /// Merges the components of `a` and `b`. Returns whether they were
+    /// distinct.
+    pub fn union(&mut self, a: usize, b: usize) -> bool {
+        let (a, b) = (self.find(a), self.find(b));
+        if a == b {
+            return false;
+        }
+
+        let (parent, child) = if self.ranks[a] < self.ranks[b] {
+            (b, a)
+        } else {
+            (a, b)
+        };
+
+        self.parents[child] = parent;
+        self.sizes[parent] += self.sizes[child];
+        if self.ranks[parent] == self.ranks[child] {
+            self.ranks[parent] += 1;
+        }
+        self.components -= 1;
+
+        true
+    }
+
+    pub fn connected(&mut self, a: usize, b: usize) -> bool {
+        self.find(a) == self.find(b)
+    }
+
+    /// Returns the size of the component of `index`.
+    pub fn size(&mut self, index: usize) -> usize {
+        let root = self.find(index);
+        self.sizes[root]
+    }
+
+    pub fn component_count(&self) -> usize {
+        self.components
+    }
+
+    /// Whether every index is in the same component.
+    pub fn is_connected(&self) -> bool {
+        self.components <= 1
+    }
+
+    /// Returns the indices of each component, by order of their smallest
+    /// index.
+    pub fn components(&mut self) -> Vec<Vec<usize>> {
+        let mut components = HashMap::new();
+        let mut order = Vec::new();
+
+        for index in 0..self.len() {
+            let root = self.find(index);
+            components
+                .entry(root)
+                .or_insert_with(|| {
+                    order.push(root);
+                    Vec::new()
+                })
+                .push(index);
+        }
+
+        order
+            .into_iter()
+            .filter_map(|root| components.remove(&root))
+            .collect()
+    }
+
+    /// Returns the sizes of the components, from the largest to the smallest.
+    pub fn component_sizes(&self) -> Vec<usize> {
+        let mut sizes = (0..self.len())
+            .filter(|&index| self.parents[index] == index)
+            .map(|root| self.sizes[root])
+            .collect::<Vec<_>>();
+        sizes.sort_unstable_by(|a, b| b.cmp(a));
+
+        sizes
+    }
+
+    /// Merges the pairs of indices in order until every index is in the same
+    /// component, as in Kruskal's algorithm with pairs sorted by weight.
+    /// Returns the number of pairs that merged two components, or `None` if
+    /// the pairs don't connect everything.
+    pub fn connect_all(
+        &mut self,
+        pairs: impl IntoIterator<Item = (usize, usize)>,
+    ) -> Option<usize> {
+        let mut merges = 0;
+        let mut pairs = pairs.into_iter();
+
+        while !self.is_connected() {
+            let (a, b) = pairs.next()?;
+            merges += usize::from(self.union(a, b));
+        }
+
+        Some(merges)
+    }
+
+    /// Merges the pairs of indices in order, as [`UnionFind::connect_all`],
+    /// and returns the position of the pair that connected everything and
+    /// that pair. Returns `None` if the pairs don't connect everything, or if
+    /// everything was already connected.
+    pub fn connecting_pair(
+        &mut self,
+        pairs: impl IntoIterator<Item = (usize, usize)>,
+    ) -> Option<(usize, (usize, usize))> {
+        if self.is_connected() {
+            return None;
+        }
+
+        pairs
+            .into_iter()
+            .enumerate()
+            .find(|&(_, (a, b))| self.union(a, b) && self.is_connected())
+    }
+}
+
+/// A disjoint-set forest over keys of any hashable type, which are added on
+/// first use. See [`UnionFind`].
+#[derive(Debug, Clone)]
+pub struct KeyedUnionFind<K> {
+    indices: HashMap<K, usize>,
+    keys: Vec<K>,
+    sets: UnionFind,
+}
+
+impl<K> Default for KeyedUnionFind<K> {
+    fn default() -> Self {
+        Self {
+            indices: HashMap::new(),
+            keys: Vec::new(),
+            sets: UnionFind::default(),
+        }
+    }
+}
+
+impl<K: Hash + Eq + Clone> KeyedUnionFind<K> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn len(&self) -> usize {
+        self.keys.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.keys.is_empty()
+    }
+
+    /// Returns the index of a key, adding it in its own component if needed.
+    pub fn insert(&mut self, key: K) -> usize {
+        if let Some(&index) = self.indices.get(&key) {
+            return index;
+        }
+
+        let index = self.sets.add();
+        self.indices.insert(key.clone(), index);
+        self.keys.push(key);
+
+        index
+    }
+
+    pub fn index(&self, key: &K) -> Option<usize> {
+        self.indices.get(key).copied()
+    }
+
+    pub fn key(&self, index: usize) -> &K {
+        &self.keys[index]
+    }
+
+    /// Returns the representative key of the component of `key`, if it was
+    /// added.
+    pub fn find(&mut self, key: &K) -> Option<K> {
+        let index = self.index(key)?;
+        let root = self.sets.find(index);
+
+        Some(self.keys[root].clone())
+    }
+
+    /// Merges the components of `a` and `b`, adding them if needed. Returns
+    /// whether they were distinct.
+    pub fn union(&mut self, a: K, b: K) -> bool {
+        let (a, b) = (self.insert(a), self.insert(b));
+        self.sets.union(a, b)
+    }
+
+    pub fn connected(&mut self, a: &K, b: &K) -> bool {
+        match (self.index(a), self.index(b)) {
+            (Some(a), Some(b)) => self.sets.connected(a, b),
+            _ => a == b,
+        }
+    }
+
+    /// Returns the size of the component of `key`, or 0 if it wasn't added.
+    pub fn size(&mut self, key: &K) -> usize {
+        self.index(key).map_or(0, |index| self.sets.size(index))
+    }
+
+    pub fn component_count(&self) -> usize {
+        self.sets.component_count()
+    }
+
+    pub fn is_connected(&self) -> bool {
+        self.sets.is_connected()
+    }
+
+    /// Returns the keys of each component, by order of insertion of their
+    /// first key.
+    pub fn components(&mut self) -> Vec<Vec<K>> {
+        self.sets
+            .components()
+            .into_iter()
+            .map(|component| {
+                component
+                    .into_iter()
+                    .map(|index| self.keys[index].clone())
+                    .collect()
+            })
+            .collect()
+    }
+
+    pub fn component_sizes(&self) -> Vec<usize> {
+        self.sets.component_sizes()
+    }
+
+    /// Merges the pairs of keys in order until every key added so far is in
+    /// the same component. Keys only seen in the pairs are added as they come,
+    /// so every key should be added with [`KeyedUnionFind::insert`] first.
+    /// Returns the number of pairs that merged two components, or `None` if
+    /// the pairs don't connect everything.
+    pub fn connect_all(&mut self, pairs: impl IntoIterator<Item = (K, K)>) -> Option<usize> {
+        let mut merges = 0;
+        let mut pairs = pairs.into_iter();
+
+        while !self.is_connected() {
+            let (a, b) = pairs.next()?;
+            merges += usize::from(self.union(a, b));
+        }
+
+        Some(merges)
+    }
+
+    /// Merges the pairs of keys in order, as [`KeyedUnionFind::connect_all`],
+    /// and returns the position of the pair that connected everything and
+    /// that pair. Returns `None` if the pairs don't connect everything, or if
+    /// everything was already connected.
+    pub fn connecting_pair(
+        &mut self,
+        pairs: impl IntoIterator<Item = (K, K)>,
+    ) -> Option<(usize, (K, K))> {
+        if self.is_connected() {
+            return None;
+        }
+
+        pairs
+            .into_iter()
+            .enumerate()
+            .find(|(_, (a, b))| self.union(a.clone(), b.clone()) && self.is_connected())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn union_and_find() {
+        let mut sets = UnionFind::new(6);
+
+        assert!(sets.union(0, 1));
+        assert!(sets.union(1, 2));
+        assert!(!sets.union(0, 2));
+        assert!(sets.union(3, 4));
+
+        assert!(sets.connected(0, 2));
+        assert!(!sets.connected(2, 3));
+        assert_eq!(sets.size(2), 3);
+        assert_eq!(sets.component_count(), 3);
+        assert_eq!(sets.components(), [vec![0, 1, 2], vec![3, 4], vec![5]]);
+        assert_eq!(sets.component_sizes(), [3, 2, 1]);
+
+        let index = sets.add();
+        assert_eq!((index, sets.len(), sets.component_count()), (6, 7, 4));
+    }
+
+    #[test]
+    fn connect_all_counts_merges() {
+        let pairs = [(0, 1), (1, 0), (2, 3), (0, 3), (4, 1), (2, 4)];
+
+        // (1, 0) merges nothing, and (2, 4) isn't needed
+        assert_eq!(UnionFind::new(5).connect_all(pairs), Some(4));
+        assert_eq!(UnionFind::new(5).connecting_pair(pairs), Some((4, (4, 1))));
+        assert_eq!(UnionFind::new(6).connect_all(pairs), None);
+        assert_eq!(UnionFind::new(6).connecting_pair(pairs), None);
+        assert_eq!(UnionFind::new(1).connect_all(pairs), Some(0));
+        assert_eq!(UnionFind::new(1).connecting_pair(pairs), None);
+    }
+
+    #[test]
+    fn keyed() {
+        let mut sets = KeyedUnionFind::new();
+        for key in ["a", "b", "c", "d"] {
+            sets.insert(key);
+        }
+
+        assert!(sets.union("a", "c"));
+        assert_eq!(sets.find(&"c"), sets.find(&"a"));
+        assert_eq!(sets.find(&"z"), None);
+        assert!(sets.connected(&"z", &"z"));
+        assert_eq!(sets.size(&"a"), 2);
+        assert_eq!(sets.components(), [vec!["a", "c"], vec!["b"], vec!["d"]]);
+
+        assert_eq!(
+            sets.connect_all([("b", "d"), ("d", "b"), ("d", "c")]),
+            Some(2)
+        );
+        assert!(sets.is_connected());
+    }
+}
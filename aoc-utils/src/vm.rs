@@ -0,0 +1,651 @@
+use std::{
+    collections::HashSet,
+    fmt,
+    ops::{Index, IndexMut},
+};
+
+use thiserror::Error;
+
+/// The kind of value an operand of an opcode accepts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OperandKind {
+    Register,
+    Value,
+    /// Either a register or a value.
+    Any,
+}
+
+/// An operand of a parsed instruction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Operand {
+    /// The index of a register, in the order of the names given to the
+    /// [`InstructionSet`].
+    Register(usize),
+    Value(i64),
+}
+
+/// An entry of the opcode table of an [`InstructionSet`]: the name of an
+/// instruction in the program, its operands, the number of cycles it takes,
+/// and the `op` the interpreter matches on to execute it, usually a variant of
+/// an enum.
+#[derive(Debug, Clone, Copy)]
+pub struct Opcode<Op> {
+    pub name: &'static str,
+    pub operands: &'static [OperandKind],
+    pub cycles: usize,
+    pub op: Op,
+}
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum ProgramError {
+    #[error("line {line}: unknown instruction \"{name}\"")]
+    UnknownInstruction { line: usize, name: String },
+    #[error("line {line}: {name} takes {expected} operands, not {found}")]
+    OperandCount {
+        line: usize,
+        name: &'static str,
+        expected: usize,
+        found: usize,
+    },
+    #[error("line {line}: invalid operand \"{operand}\" for {name}")]
+    InvalidOperand {
+        line: usize,
+        name: &'static str,
+        operand: String,
+    },
+}
+
+/// An instruction of a parsed program.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Instruction<Op> {
+    pub name: &'static str,
+    pub op: Op,
+    pub operands: Vec<Operand>,
+    pub cycles: usize,
+}
+
+impl<Op> Instruction<Op> {
+    /// Returns the register of an operand.
+    ///
+    /// # Panics
+    ///
+    /// If the operand is a value, which the opcode table should prevent.
+    pub fn register(&self, operand: usize) -> usize {
+        match self.operands[operand] {
+            Operand::Register(register) => register,
+            Operand::Value(value) => panic!("{} expects a register, not {value}", self.name),
+        }
+    }
+}
+
+impl<Op> fmt::Display for Instruction<Op> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name)?;
+        for (i, operand) in self.operands.iter().enumerate() {
+            let separator = if i == 0 { " " } else { ", " };
+            match operand {
+                Operand::Register(register) => write!(f, "{separator}r{register}")?,
+                Operand::Value(value) => write!(f, "{separator}{value}")?,
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// The instructions and registers of a machine, from which programs are
+/// parsed. Each line of a program is an instruction, whose name is followed by
+/// its operands, separated by spaces or commas. Operands are register names or
+/// integers.
+#[derive(Debug, Clone)]
+pub struct InstructionSet<Op> {
+    registers: Vec<&'static str>,
+    opcodes: Vec<Opcode<Op>>,
+}
+
+impl<Op: Copy> InstructionSet<Op> {
+    pub fn new(registers: &[&'static str], opcodes: &[Opcode<Op>]) -> Self {
+        Self {
+            registers: registers.to_vec(),
+            opcodes: opcodes.to_vec(),
+        }
+    }
+
+    /// Returns the index of a register from its name.
+    pub fn register(&self, name: &str) -> Option<usize> {
+        self.registers.iter().position(|&register| register == name)
+    }
+
+    pub fn register_count(&self) -> usize {
+        self.registers.len()
+    }
+
+    /// Parses a program, skipping empty lines.
+    pub fn parse(&self, program: &str) -> Result<Vec<Instruction<Op>>, ProgramError> {
+        program
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty())
+            .map(|(i, line)| self.parse_instruction(i + 1, line))
+            .collect()
+    }
+
+    fn parse_instruction(&self, line: usize, text: &str) -> Result<Instruction<Op>, ProgramError> {
+        let mut tokens = text
+            .split(|c: char| c.is_whitespace() || c == ',')
+            .filter(|token| !token.is_empty());
+        let name = tokens.next().unwrap_or_default();
+
+        let opcode = self
+            .opcodes
+            .iter()
+            .find(|opcode| opcode.name == name)
+            .ok_or_else(|| ProgramError::UnknownInstruction {
+                line,
+                name: name.to_string(),
+            })?;
+
+        let operands = tokens
+            .map(|token| {
+                self.parse_operand(token)
+                    .ok_or_else(|| ProgramError::InvalidOperand {
+                        line,
+                        name: opcode.name,
+                        operand: token.to_string(),
+                    })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        if operands.len() != opcode.operands.len() {
+            return Err(ProgramError::OperandCount {
+                line,
+                name: opcode.name,
+                expected: opcode.operands.len(),
+                found: operands.len(),
+            });
+        }
+
+        for (operand, kind) in operands.iter().zip(opcode.operands) {
+            let valid = matches!(
+                (operand, kind),
+                (_, OperandKind::Any)
+                    | (Operand::Register(_), OperandKind::Register)
+                    | (Operand::Value(_), OperandKind::Value)
+            );
+
+            if !valid {
+                return Err(ProgramError::InvalidOperand {
+                    line,
+                    name: opcode.name,
+                    operand: match operand {
+                        Operand::Register(register) => self.registers[*register].to_string(),
+                        Operand::Value(value) => value.to_string(),
+                    },
+                });
+            }
+        }
+
+        Ok(Instruction {
+            name: opcode.name,
+            op: opcode.op,
+            operands,
+            cycles: opcode.cycles,
+        })
+    }
+
+    fn parse_operand(&self, token: &str) -> Option<Operand> {
+        match self.register(token) {
+            Some(register) => Some(Operand::Register(register)),
+            None => token.parse().ok().map(Operand::Value),
+        }
+    }
+}
+
+/// The registers of a [`Machine`], indexed in the order of the names given to
+/// the [`InstructionSet`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Registers(pub Vec<i64>);
+
+impl Registers {
+    /// Returns the value of an operand: the content of a register, or the
+    /// value itself.
+    pub fn get(&self, operand: Operand) -> i64 {
+        match operand {
+            Operand::Register(register) => self.0[register],
+            Operand::Value(value) => value,
+        }
+    }
+}
+
+impl Index<usize> for Registers {
+    type Output = i64;
+
+    fn index(&self, register: usize) -> &i64 {
+        &self.0[register]
+    }
+}
+
+impl IndexMut<usize> for Registers {
+    fn index_mut(&mut self, register: usize) -> &mut i64 {
+        &mut self.0[register]
+    }
+}
+
+/// Where the execution goes after an instruction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Flow {
+    /// To the following instruction.
+    Next,
+    /// To the instruction at an offset from the current one.
+    Jump(i64),
+    /// To the instruction at an index.
+    Goto(usize),
+    Halt,
+}
+
+/// Why a [`Machine`] stopped running.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Stop {
+    /// The program halted, or jumped outside of itself.
+    Halted,
+    /// The next instruction, at this index, has a breakpoint. Running again
+    /// executes it.
+    Breakpoint(usize),
+    /// The next instruction, at this index, would start a loop, as detected
+    /// by the [`LoopDetection`] of the machine.
+    Loop(usize),
+    /// The cycle limit of the machine was reached.
+    CycleLimit,
+}
+
+/// How a [`Machine`] detects that its program loops forever.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LoopDetection {
+    #[default]
+    Never,
+    /// Stops before executing an instruction a second time, which is a loop
+    /// for programs without conditional jumps.
+    Instruction,
+    /// Stops when an instruction is about to be executed with the same
+    /// registers as before, which is always an infinite loop.
+    State,
+}
+
+/// A cycle of the execution of a [`Machine`], given to the tracing function.
+/// The registers are the ones during the cycle, before the instruction has
+/// any effect.
+#[derive(Debug)]
+pub struct Tick<'a, Op> {
+    /// The number of the cycle, starting at 1.
+    pub cycle: usize,
+    pub pc: usize,
+    pub instruction: &'a Instruction<Op>,
+    pub registers: &'a Registers,
+}
+
+/// A machine running a program, whose instructions are executed by a function
+/// given to [`Machine::run`], so that an interpreter is mostly its opcode
+/// table and that function.
+#[derive(Debug, Clone)]
+pub struct Machine<Op> {
+    program: Vec<Instruction<Op>>,
+    pub registers: Registers,
+    pc: usize,
+    cycle: usize,
+    halted: bool,
+    breakpoints: HashSet<usize>,
+    /// Set when stopped at a breakpoint, to go past it when running again.
+    resuming: bool,
+    loop_detection: LoopDetection,
+    executed: HashSet<usize>,
+    states: HashSet<(usize, Registers)>,
+    cycle_limit: Option<usize>,
+}
+
+impl<Op> Machine<Op> {
+    /// Creates a machine with `register_count` registers set to 0.
+    pub fn new(program: Vec<Instruction<Op>>, register_count: usize) -> Self {
+        Self {
+            program,
+            registers: Registers(vec![0; register_count]),
+            pc: 0,
+            cycle: 0,
+            halted: false,
+            breakpoints: HashSet::new(),
+            resuming: false,
+            loop_detection: LoopDetection::Never,
+            executed: HashSet::new(),
+            states: HashSet::new(),
+            cycle_limit: None,
+        }
+    }
+
+    pub fn with_loop_detection(mut self, loop_detection: LoopDetection) -> Self {
+        self.loop_detection = loop_detection;
+        self
+    }
+
+    /// Stops the machine once it has run for `limit` cycles.
+    pub fn with_cycle_limit(mut self, limit: usize) -> Self {
+        self.cycle_limit = Some(limit);
+        self
+    }
+
+    pub fn program(&self) -> &[Instruction<Op>] {
+        &self.program
+    }
+
+    /// Returns the index of the next instruction.
+    pub fn pc(&self) -> usize {
+        self.pc
+    }
+
+    /// Returns the number of cycles run so far.
+    pub fn cycle(&self) -> usize {
+        self.cycle
+    }
+
+    pub fn is_halted(&self) -> bool {
+        self.halted
+    }
+
+    pub fn add_breakpoint(&mut self, pc: usize) {
+        self.breakpoints.insert(pc);
+    }
+
+    pub fn remove_breakpoint(&mut self, pc: usize) {
+        self.breakpoints.remove(&pc);
+    }
+
+    /// Runs the program until it stops, executing each instruction with
+    /// `execute`, which gets the registers and returns where to go next.
+    pub fn run(&mut self, execute: impl FnMut(&mut Registers, &Instruction<Op>) -> Flow) -> Stop {
+        self.run_traced(execute, |_| {})
+    }
+
+    /// Runs the program as [`Machine::run`], calling `trace` for every cycle.
+    pub fn run_traced(
+        &mut self,
+        mut execute: impl FnMut(&mut Registers, &Instruction<Op>) -> Flow,
+        mut trace: impl FnMut(Tick<'_, Op>),
+    ) -> Stop {
+        loop {
+            if let Some(stop) = self.step_traced(&mut execute, &mut trace) {
+                return stop;
+            }
+        }
+    }
+
+    /// Executes a single instruction, unless the machine has to stop first.
+    pub fn step(
+        &mut self,
+        mut execute: impl FnMut(&mut Registers, &Instruction<Op>) -> Flow,
+    ) -> Option<Stop> {
+        self.step_traced(&mut execute, &mut |_| {})
+    }
+
+    fn step_traced(
+        &mut self,
+        execute: &mut impl FnMut(&mut Registers, &Instruction<Op>) -> Flow,
+        trace: &mut impl FnMut(Tick<'_, Op>),
+    ) -> Option<Stop> {
+        let pc = self.pc;
+        if self.halted || pc >= self.program.len() {
+            self.halted = true;
+            return Some(Stop::Halted);
+        }
+        if self.cycle_limit.is_some_and(|limit| self.cycle >= limit) {
+            return Some(Stop::CycleLimit);
+        }
+        if !std::mem::take(&mut self.resuming) && self.breakpoints.contains(&pc) {
+            self.resuming = true;
+            return Some(Stop::Breakpoint(pc));
+        }
+
+        let looping = match self.loop_detection {
+            LoopDetection::Never => false,
+            LoopDetection::Instruction => !self.executed.insert(pc),
+            LoopDetection::State => !self.states.insert((pc, self.registers.clone())),
+        };
+        if looping {
+            return Some(Stop::Loop(pc));
+        }
+
+        let instruction = &self.program[pc];
+        for _ in 0..instruction.cycles {
+            self.cycle += 1;
+            trace(Tick {
+                cycle: self.cycle,
+                pc,
+                instruction,
+                registers: &self.registers,
+            });
+        }
+
+        let target = match execute(&mut self.registers, instruction) {
+            Flow::Next => pc.checked_add(1),
+            Flow::Jump(offset) => pc.checked_add_signed(offset as isize),
+            Flow::Goto(target) => Some(target),
+            Flow::Halt => None,
+        };
+
+        match target {
+            Some(target) => self.pc = target,
+            None => self.halted = true,
+        }
+
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    enum Cpu {
+        Noop,
+        Addx,
+    }
+
+    fn cpu() -> InstructionSet<Cpu> {
+        InstructionSet::new(
+            &["x"],
+            &[
+                Opcode {
+                    name: "noop",
+                    operands: &[],
+                    cycles: 1,
+                    op: Cpu::Noop,
+                },
+                Opcode {
+                    name: "addx",
+                    operands: &[OperandKind::Value],
+                    cycles: 2,
+                    op: Cpu::Addx,
+                },
+            ],
+        )
+    }
+
+    fn execute(registers: &mut Registers, instruction: &Instruction<Cpu>) -> Flow {
+        if instruction.op == Cpu::Addx {
+            registers[0] += registers.get(instruction.operands[0]);
+        }
+
+        Flow::Next
+    }
+
+    #[test]
+    fn cycle_timing() {
+        let program = cpu().parse("noop\naddx 3\naddx -5\n").unwrap();
+        let mut machine = Machine::new(program, 1);
+        machine.registers[0] = 1;
+        let mut during = Vec::new();
+
+        let stop = machine.run_traced(execute, |tick| {
+            during.push((tick.cycle, tick.pc, tick.registers[0]));
+        });
+
+        assert_eq!(stop, Stop::Halted);
+        assert_eq!(
+            during,
+            [(1, 0, 1), (2, 1, 1), (3, 1, 1), (4, 2, 4), (5, 2, 4)]
+        );
+        assert_eq!((machine.cycle(), machine.registers[0]), (5, -1));
+    }
+
+    #[test]
+    fn cathode_ray_tube() {
+        let program = cpu().parse(include_str!("vm_example.txt")).unwrap();
+        let mut machine = Machine::new(program, 1);
+        machine.registers[0] = 1;
+        let mut strength = 0;
+        let mut screen = String::new();
+
+        machine.run_traced(execute, |tick| {
+            let x = tick.registers[0];
+            if tick.cycle % 40 == 20 {
+                strength += tick.cycle as i64 * x;
+            }
+
+            let column = (tick.cycle - 1) % 40;
+            screen.push(if x.abs_diff(column as i64) <= 1 {
+                '#'
+            } else {
+                '.'
+            });
+            if column == 39 {
+                screen.push('\n');
+            }
+        });
+
+        assert_eq!(strength, 13140);
+        assert_eq!(
+            screen,
+            "##..##..##..##..##..##..##..##..##..##..
+###...###...###...###...###...###...###.
+####....####....####....####....####....
+#####.....#####.....#####.....#####.....
+######......######......######......####
+#######.......#######.......#######.....
+"
+        );
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    enum Console {
+        Acc,
+        Jmp,
+        Nop,
+    }
+
+    const BOOT_CODE: &str = "nop +0
+acc +1
+jmp +4
+acc +3
+jmp -3
+acc -99
+acc +1
+jmp -4
+acc +6";
+
+    fn boot(program: Vec<Instruction<Console>>, loop_detection: LoopDetection) -> (Stop, i64) {
+        let mut machine = Machine::new(program, 1).with_loop_detection(loop_detection);
+        let stop = machine.run(|registers, instruction| {
+            let value = registers.get(instruction.operands[0]);
+            match instruction.op {
+                Console::Acc => registers[0] += value,
+                Console::Jmp => return Flow::Jump(value),
+                Console::Nop => {}
+            }
+
+            Flow::Next
+        });
+
+        (stop, machine.registers[0])
+    }
+
+    fn console() -> InstructionSet<Console> {
+        let opcode = |name, op| Opcode {
+            name,
+            operands: &[OperandKind::Value],
+            cycles: 1,
+            op,
+        };
+
+        InstructionSet::new(
+            &[],
+            &[
+                opcode("acc", Console::Acc),
+                opcode("jmp", Console::Jmp),
+                opcode("nop", Console::Nop),
+            ],
+        )
+    }
+
+    #[test]
+    fn handheld_halting() {
+        let mut program = console().parse(BOOT_CODE).unwrap();
+        assert_eq!(
+            boot(program.clone(), LoopDetection::Instruction),
+            (Stop::Loop(1), 5)
+        );
+
+        program[7].op = Console::Nop;
+        assert_eq!(boot(program, LoopDetection::Instruction), (Stop::Halted, 8));
+
+        let endless = console().parse("jmp 0").unwrap();
+        assert_eq!(boot(endless, LoopDetection::State), (Stop::Loop(0), 0));
+    }
+
+    #[test]
+    fn breakpoints_and_limits() {
+        let program = console().parse(BOOT_CODE).unwrap();
+        let execute = |registers: &mut Registers, instruction: &Instruction<Console>| {
+            if instruction.op == Console::Acc {
+                registers[0] += registers.get(instruction.operands[0]);
+            }
+            Flow::Next
+        };
+
+        let mut machine = Machine::new(program.clone(), 1);
+        machine.add_breakpoint(3);
+        assert_eq!(machine.run(execute), Stop::Breakpoint(3));
+        assert_eq!((machine.pc(), machine.registers[0]), (3, 1));
+        assert_eq!(machine.step(execute), None);
+        assert_eq!(machine.registers[0], 4);
+        machine.remove_breakpoint(3);
+        assert_eq!(machine.run(execute), Stop::Halted);
+        assert!(machine.is_halted());
+
+        let mut machine = Machine::new(program, 1).with_cycle_limit(4);
+        assert_eq!(machine.run(execute), Stop::CycleLimit);
+        assert_eq!((machine.cycle(), machine.pc()), (4, 4));
+    }
+
+    #[test]
+    fn parse_errors() {
+        let cpu = cpu();
+
+        assert_eq!(cpu.register("x"), Some(0));
+        assert_eq!(cpu.register_count(), 1);
+        assert_eq!(
+            cpu.parse("noop\n\naddx x").unwrap_err(),
+            ProgramError::InvalidOperand {
+                line: 3,
+                name: "addx",
+                operand: "x".to_string()
+            }
+        );
+        assert_eq!(
+            cpu.parse("addx 1 2").unwrap_err().to_string(),
+            "line 1: addx takes 1 operands, not 2"
+        );
+        assert_eq!(
+            cpu.parse("mulx 3").unwrap_err().to_string(),
+            "line 1: unknown instruction \"mulx\""
+        );
+        assert_eq!(cpu.parse("addx, -3").unwrap()[0].to_string(), "addx -3");
+    }
+}
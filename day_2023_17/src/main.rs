@@ -1,6 +1,5 @@
 use std::collections::{BinaryHeap, HashMap, HashSet};
 
-const INPUT: &str = include_str!("input.txt");
 const _EXAMPLE: &str = r#"2413432311323
 3215453535623
 3255245654254
@@ -239,7 +238,7 @@ fn part_2(input: &str) {
 }
 
 fn main() {
-    let input = INPUT;
+    let input = &aoc_utils::input!();
     part_1(input);
     part_2(input);
 }
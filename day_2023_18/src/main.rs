@@ -3,7 +3,6 @@ use std::{collections::HashSet, str::FromStr};
 use aoc_utils::*;
 use itertools::Itertools;
 
-const INPUT: &str = include_str!("input.txt");
 const EXAMPLE: &str = r#"R 6 (#70c710)
 D 5 (#0dc571)
 L 2 (#5713f0)
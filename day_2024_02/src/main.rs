@@ -2,7 +2,6 @@ use std::{matches, ops::ControlFlow};
 
 use itertools::Itertools;
 
-const INPUT: &str = include_str!("input.txt");
 const _EXAMPLE: &str = r#"7 6 4 2 1
 1 2 7 8 9
 9 7 6 2 1
@@ -70,7 +69,7 @@ fn part_2(input: &str) {
 }
 
 fn main() {
-    let input = INPUT;
+    let input = &aoc_utils::input!();
     part_1(input);
     part_2(input);
 }
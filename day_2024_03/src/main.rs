@@ -8,7 +8,6 @@ use nom::{
     IResult,
 };
 
-const INPUT: &str = include_str!("input.txt");
 const _EXAMPLE: &str = r#"xmul(2,4)%&mul[3,7]!@^do_not_mul(5,5)+mul(32,64]then(mul(11,8)mul(8,5))"#;
 
 enum Instruction {
@@ -79,7 +78,7 @@ fn part_2(input: &str) {
 }
 
 fn main() {
-    let input = INPUT;
+    let input = &aoc_utils::input!();
     part_1(input);
     part_2(input);
 }
@@ -5,7 +5,6 @@ use std::{
     str::FromStr,
 };
 
-const INPUT: &str = include_str!("input.txt");
 const _EXAMPLE: &str = r#"47|53
 97|13
 97|61
@@ -160,7 +159,7 @@ fn part_2(input: &str) {
 }
 
 fn main() {
-    let input = INPUT;
+    let input = &aoc_utils::input!();
     part_1(input);
     part_2(input);
 }
@@ -2,7 +2,6 @@ use std::{collections::HashSet, convert::Infallible, ops::ControlFlow, str::From
 
 use itertools::Itertools;
 
-const INPUT: &str = include_str!("input.txt");
 const _EXAMPLE: &str = r#"....#.....
 .........#
 ..........
@@ -174,7 +173,7 @@ fn part_2(input: &str) {
 }
 
 fn main() {
-    let input = INPUT;
+    let input = &aoc_utils::input!();
     part_1(input);
     part_2(input);
 }
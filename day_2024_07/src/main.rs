@@ -2,7 +2,6 @@ use std::{convert::Infallible, str::FromStr};
 
 use itertools::Itertools;
 
-const INPUT: &str = include_str!("input.txt");
 const _EXAMPLE: &str = r#"190: 10 19
 3267: 81 40 27
 83: 17 5
@@ -97,7 +96,7 @@ fn part_2(input: &str) {
 }
 
 fn main() {
-    let input = INPUT;
+    let input = &aoc_utils::input!();
     part_1(input);
     part_2(input);
 }
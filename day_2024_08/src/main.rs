@@ -6,7 +6,6 @@ use std::{
 
 use itertools::Itertools;
 
-const INPUT: &str = include_str!("input.txt");
 const _EXAMPLE: &str = r#"............
 ........0...
 .....0......
@@ -126,7 +125,7 @@ fn part_2(input: &str) {
 }
 
 fn main() {
-    let input = INPUT;
+    let input = &aoc_utils::input!();
     part_1(input);
     part_2(input);
 }
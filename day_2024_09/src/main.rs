@@ -1,6 +1,5 @@
 use std::{convert::Infallible, str::FromStr};
 
-const INPUT: &str = include_str!("input.txt");
 const _EXAMPLE: &str = r#"2333133121414131402"#;
 
 fn checksum<I>(i: I) -> usize
@@ -149,7 +148,7 @@ fn part_2(input: &str) {
 }
 
 fn main() {
-    let input = INPUT;
+    let input = &aoc_utils::input!();
     part_1(input);
     part_2(input);
 }
@@ -4,7 +4,6 @@ use std::{
     str::FromStr,
 };
 
-const INPUT: &str = include_str!("input.txt");
 const _EXAMPLE: &str = r#"89010123
 78121874
 87430965
@@ -128,7 +127,7 @@ fn part_2(input: &str) {
 }
 
 fn main() {
-    let input = INPUT;
+    let input = &aoc_utils::input!();
     part_1(input);
     part_2(input);
 }
@@ -1,6 +1,5 @@
 use std::{collections::HashMap, convert::Infallible, str::FromStr};
 
-const INPUT: &str = include_str!("input.txt");
 const _EXAMPLE: &str = r#"125 17"#;
 
 fn split(mut n: u64) -> (u64, u64) {
@@ -96,7 +95,7 @@ fn part_2(input: &str) {
 }
 
 fn main() {
-    let input = INPUT;
+    let input = &aoc_utils::input!();
     part_1(input);
     part_2(input);
 }
@@ -1,6 +1,5 @@
 use std::{convert::Infallible, str::FromStr};
 
-const INPUT: &str = include_str!("input.txt");
 const _EXAMPLE: &str = r#"Button A: X+94, Y+34
 Button B: X+22, Y+67
 Prize: X=8400, Y=5400
@@ -121,7 +120,7 @@ fn part_2(input: &str) {
 }
 
 fn main() {
-    let input = INPUT;
+    let input = &aoc_utils::input!();
     part_1(input);
     part_2(input);
 }
@@ -1,6 +1,5 @@
 use std::{cmp::Ordering, convert::Infallible, str::FromStr};
 
-const INPUT: &str = include_str!("input.txt");
 const _EXAMPLE: &str = r#"p=0,4 v=3,-3
 p=6,3 v=-1,-3
 p=10,3 v=-1,2
@@ -131,7 +130,7 @@ fn part_2(input: &str) {
 }
 
 fn main() {
-    let input = INPUT;
+    let input = &aoc_utils::input!();
     part_1(input);
     part_2(input);
 }
@@ -1,6 +1,5 @@
 use std::{collections::HashSet, convert::Infallible, hash::Hash, str::FromStr};
 
-const INPUT: &str = include_str!("input.txt");
 const _EXAMPLE: &str = r#"##########
 #..O..O.O#
 #......O.#
@@ -353,7 +352,7 @@ fn part_2(input: &str) {
 }
 
 fn main() {
-    let input = INPUT;
+    let input = &aoc_utils::input!();
     part_1(input);
     part_2(input);
 }
@@ -1,20 +0,0 @@
-use aoc_utils::*;
-use itertools::Itertools;
-
-const INPUT: &str = include_str!("input.txt");
-const EXAMPLE: &str = r#"
-"#;
-
-fn part_1(input: &str) {
-    // part 1
-}
-
-fn part_2(input: &str) {
-    // part 2
-}
-
-fn main() {
-    let input = INPUT;
-    part_1(input);
-    //part_2(input);
-}
@@ -0,0 +1,19 @@
+use aoc_utils::*;
+use itertools::Itertools;
+
+// Day {{day}} ({{year}}): {{title}}
+
+const EXAMPLE: &str = r#"{{example}}"#;
+
+fn part_1(input: &str) -> Option<u64> {
+    // part 1: return `Some(answer)` once solved
+    None
+}
+
+fn part_2(input: &str) -> Option<u64> {
+    // part 2: return `Some(answer)` once solved
+    None
+}
+
+main!(part_1);
+//main!(part_1, part_2);
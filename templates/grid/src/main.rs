@@ -0,0 +1,66 @@
+use std::{convert::Infallible, str::FromStr};
+
+use aoc_utils::*;
+use itertools::Itertools;
+
+// Day {{day}} ({{year}}): {{title}}
+
+const EXAMPLE: &str = r#"{{example}}"#;
+
+struct Grid {
+    tiles: Vec<Vec<char>>,
+}
+
+impl FromStr for Grid {
+    type Err = Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(Grid {
+            tiles: s.lines().map(|l| l.chars().collect()).collect(),
+        })
+    }
+}
+
+impl Grid {
+    fn width(&self) -> usize {
+        self.tiles[0].len()
+    }
+
+    fn height(&self) -> usize {
+        self.tiles.len()
+    }
+
+    fn get(&self, x: usize, y: usize) -> Option<char> {
+        self.tiles.get(y).and_then(|l| l.get(x)).copied()
+    }
+
+    fn all_coordinates(&self) -> impl Iterator<Item = (usize, usize)> + '_ {
+        (0..self.height()).flat_map(move |y| (0..self.width()).map(move |x| (x, y)))
+    }
+
+    fn adjacent(&self, x: usize, y: usize) -> impl Iterator<Item = (usize, usize)> + '_ {
+        [(0, -1), (1, 0), (0, 1), (-1, 0)]
+            .into_iter()
+            .filter_map(move |(dx, dy)| {
+                let x = x.checked_add_signed(dx).filter(|x| *x < self.width())?;
+                let y = y.checked_add_signed(dy).filter(|y| *y < self.height())?;
+
+                Some((x, y))
+            })
+    }
+}
+
+fn part_1(input: &str) -> Option<u64> {
+    let grid: Grid = input.parse().unwrap();
+    // part 1: return `Some(answer)` once solved
+    None
+}
+
+fn part_2(input: &str) -> Option<u64> {
+    let grid: Grid = input.parse().unwrap();
+    // part 2: return `Some(answer)` once solved
+    None
+}
+
+main!(part_1);
+//main!(part_1, part_2);
@@ -0,0 +1,30 @@
+use aoc_utils::*;
+use itertools::Itertools;
+use nom::{
+    character::complete::{line_ending, u64},
+    multi::separated_list1,
+    IResult,
+};
+
+// Day {{day}} ({{year}}): {{title}}
+
+const EXAMPLE: &str = r#"{{example}}"#;
+
+fn parse(input: &str) -> IResult<&str, Vec<u64>> {
+    separated_list1(line_ending, u64)(input)
+}
+
+fn part_1(input: &str) -> Option<u64> {
+    let (_, values) = parse(input).unwrap();
+    // part 1: return `Some(answer)` once solved
+    None
+}
+
+fn part_2(input: &str) -> Option<u64> {
+    let (_, values) = parse(input).unwrap();
+    // part 2: return `Some(answer)` once solved
+    None
+}
+
+main!(part_1);
+//main!(part_1, part_2);